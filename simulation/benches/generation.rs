@@ -0,0 +1,41 @@
+use criterion::{
+  black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use simulation::{NullCanvas, World};
+
+const SEED: u64 = 0x5eed;
+const SIZES: [usize; 3] = [128, 256, 512];
+
+fn next_generation(c: &mut Criterion) {
+  let mut group = c.benchmark_group("next_generation");
+  for size in SIZES {
+    group.throughput(Throughput::Elements((size * size) as u64));
+    group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, &size| {
+      // the board settles as it runs, so every sample starts again from the same soup
+      b.iter_batched_ref(
+        || World::random_from_seed(size, size, SEED),
+        |world| world.next_generation(&mut NullCanvas),
+        BatchSize::LargeInput,
+      );
+    });
+  }
+  group.finish();
+}
+
+fn random(c: &mut Criterion) {
+  let mut group = c.benchmark_group("random");
+  for size in SIZES {
+    group.throughput(Throughput::Elements((size * size) as u64));
+    group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, &size| {
+      let mut rng = StdRng::seed_from_u64(SEED);
+      b.iter(|| World::random(black_box(size), black_box(size), &mut rng));
+    });
+  }
+  group.finish();
+}
+
+criterion_group!(benches, next_generation, random);
+criterion_main!(benches);
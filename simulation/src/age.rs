@@ -0,0 +1,76 @@
+use std::io::{self, BufWriter, Stdout, Write};
+
+use crate::{Canvas, World, ON_COLOUR};
+
+// ansi 256-colour ramp from fresh (white) to old (red)
+const GRADIENT: [u8; 7] = [231, 226, 220, 214, 208, 202, 196];
+// generations spent on each shade of the ramp
+const AGE_STEP: u8 = 4;
+
+// colours live cells by age; ages are only known to the world, so sync it before rendering
+pub struct AgeCanvas {
+  width: usize,
+  height: usize,
+  alive: Vec<bool>,
+  ages: Vec<u8>,
+  stdout: Stdout,
+}
+
+impl AgeCanvas {
+  pub fn new(width: usize, height: usize) -> Self {
+    Self {
+      width,
+      height,
+      alive: vec![false; width * height],
+      ages: vec![0; width * height],
+      stdout: io::stdout(),
+    }
+  }
+
+  pub fn sync(&mut self, world: &World) {
+    for (alive, cell) in self.alive.iter_mut().zip(&world.cells) {
+      *alive = cell.is_alive();
+    }
+    for (age, world_age) in self.ages.iter_mut().zip(world.ages()) {
+      *age = world_age;
+    }
+  }
+
+  #[inline]
+  pub fn colour_for_age(age: u8) -> u8 {
+    GRADIENT[((age / AGE_STEP) as usize).min(GRADIENT.len() - 1)]
+  }
+}
+
+impl Canvas for AgeCanvas {
+  type Colour = u8;
+
+  #[inline]
+  fn draw_pixel(&mut self, i: usize, j: usize, colour: Self::Colour) {
+    let idx = i * self.width + j;
+    self.alive[idx] = colour & 0x1 == ON_COLOUR;
+    self.ages[idx] = 0;
+  }
+
+  fn render(&self) -> io::Result<()> {
+    let mut buf = BufWriter::new(self.stdout.lock());
+    for i in 0..self.height {
+      for j in 0..self.width {
+        let idx = i * self.width + j;
+        if self.alive[idx] {
+          let colour = Self::colour_for_age(self.ages[idx]);
+          write!(buf, "\x1B[38;5;{colour}m @ \x1B[0m")?;
+        } else {
+          buf.write_all(b" . ")?;
+        }
+      }
+      buf.write_all(b"\n")?;
+    }
+    buf.flush()
+  }
+
+  #[inline]
+  fn dimensions(&self) -> Option<(usize, usize)> {
+    Some((self.width, self.height))
+  }
+}
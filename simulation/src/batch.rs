@@ -0,0 +1,37 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::{NullCanvas, Rule, World};
+
+// the final population of a 50% random board per seed, in seed order; each seed builds the same
+// board World::random_from_seed would under the given rule
+pub fn run_batch(
+  width: usize,
+  height: usize,
+  rule: Rule,
+  seeds: &[u64],
+  generations: u64,
+) -> Vec<usize> {
+  #[cfg(feature = "rayon")]
+  {
+    use rayon::prelude::*;
+    seeds
+      .par_iter()
+      .map(|&seed| final_population(width, height, rule, seed, generations))
+      .collect()
+  }
+  #[cfg(not(feature = "rayon"))]
+  seeds
+    .iter()
+    .map(|&seed| final_population(width, height, rule, seed, generations))
+    .collect()
+}
+
+fn final_population(width: usize, height: usize, rule: Rule, seed: u64, generations: u64) -> usize {
+  let mut world = World::with_rule(width, height, rule);
+  world.populate(0.5, &mut StdRng::seed_from_u64(seed));
+  for _ in 0..generations {
+    world.next_generation(&mut NullCanvas);
+  }
+  world.population()
+}
@@ -0,0 +1,46 @@
+use std::io::{self, Read, Write};
+
+use crate::World;
+
+const MAGIC: &[u8; 4] = b"LIFE";
+const VERSION: u8 = 1;
+
+impl World {
+  // `LIFE`, a version byte, then width and height as little endian u32s, followed by
+  // alive_bitmap; rule and topology are not stored
+  pub fn write_binary(&self, w: &mut impl Write) -> io::Result<()> {
+    let dimension = |n: usize| {
+      u32::try_from(n).map_err(|_| invalid_data(format!("dimension {n} does not fit in a u32")))
+    };
+    w.write_all(MAGIC)?;
+    w.write_all(&[VERSION])?;
+    w.write_all(&dimension(self.width)?.to_le_bytes())?;
+    w.write_all(&dimension(self.height)?.to_le_bytes())?;
+    w.write_all(&self.alive_bitmap())
+  }
+
+  pub fn read_binary(r: &mut impl Read) -> io::Result<World> {
+    let mut header = [0; 13];
+    r.read_exact(&mut header)?;
+    if &header[..4] != MAGIC {
+      return Err(invalid_data(String::from("not a binary world snapshot")));
+    }
+    if header[4] != VERSION {
+      return Err(invalid_data(format!(
+        "unsupported snapshot version {}",
+        header[4]
+      )));
+    }
+    let width = u32::from_le_bytes(header[5..9].try_into().unwrap()) as usize;
+    let height = u32::from_le_bytes(header[9..13].try_into().unwrap()) as usize;
+    let mut world = World::try_new(width, height).map_err(|err| invalid_data(err.to_string()))?;
+    let mut bitmap = vec![0; world.cells.len().div_ceil(8)];
+    r.read_exact(&mut bitmap)?;
+    world.set_alive_bitmap(&bitmap);
+    Ok(world)
+  }
+}
+
+fn invalid_data(msg: String) -> io::Error {
+  io::Error::new(io::ErrorKind::InvalidData, msg)
+}
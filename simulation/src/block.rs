@@ -0,0 +1,95 @@
+use crate::{Canvas, ProductSingletonCandidate, Stats, World};
+
+// transition table of a 2x2 block automaton on the margolus neighbourhood; a block's state has
+// its nw, ne, sw and se cells as bits 1, 2, 4 and 8, and the table gives the state it becomes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockRule {
+  pub table: [u8; 16],
+}
+
+impl BlockRule {
+  // blocks of two are left alone, every other block is inverted, and inverted blocks that had
+  // three live cells are also turned half way round
+  pub const CRITTERS: BlockRule = BlockRule {
+    table: [15, 14, 13, 3, 11, 5, 6, 1, 7, 9, 10, 2, 12, 4, 8, 0],
+  };
+
+  pub fn new(table: [u8; 16]) -> Self {
+    assert!(
+      table.iter().all(|&state| state < 16),
+      "block states only have four cells"
+    );
+    Self { table }
+  }
+}
+
+impl World {
+  // applies the block rule once, tiling the board with 2x2 blocks from (0, 0) on even generations
+  // and from (1, 1) on odd ones; returns the number of cells that toggled
+  pub fn next_generation_block<Co, Ca>(&mut self, rule: &BlockRule, canvas: &mut Ca) -> usize
+  where
+    Co: ProductSingletonCandidate<Co, Co>,
+    Ca: Canvas<Colour = Co>,
+  {
+    self.check_canvas(canvas);
+    self.evolve_block(rule, |i, j, alive| {
+      canvas.draw_pixel(i, j, if alive { Co::FST } else { Co::SND });
+    })
+  }
+
+  pub fn step_block(&mut self, rule: &BlockRule) -> usize {
+    self.evolve_block(rule, |_, _, _| {})
+  }
+
+  // blocks of one partition never overlap, so each is decided and applied in turn. a block that
+  // would hang off the edge is left as it is, unless the topology wraps it onto an even side,
+  // where it cannot meet a block from the same partition
+  fn evolve_block<F>(&mut self, rule: &BlockRule, mut on_change: F) -> usize
+  where
+    F: FnMut(usize, usize, bool),
+  {
+    let offset = (self.generation % 2) as usize;
+    self.generation += 1;
+    let mut changes = core::mem::take(&mut self.changes);
+    changes.clear();
+    for i in (offset..self.height).step_by(2) {
+      for j in (offset..self.width).step_by(2) {
+        let Some(block) = self.block_at(i, j) else {
+          continue;
+        };
+        let state = block.iter().enumerate().fold(0, |state, (n, &(i, j))| {
+          state | (u8::from(self.get(i, j)) << n)
+        });
+        let next = rule.table[state as usize];
+        for (n, &(i, j)) in block.iter().enumerate() {
+          let alive = next & (1 << n) != 0;
+          if alive != self.get(i, j) {
+            changes.push((i, j, alive));
+          }
+        }
+      }
+    }
+    self.stats = Stats::default();
+    for &(i, j, alive) in &changes {
+      self.apply_change(i, j, alive, &mut on_change);
+    }
+    self.changes = changes;
+    self.stats.births + self.stats.deaths
+  }
+
+  // nw, ne, sw and se positions of the block with its top left cell at (i, j)
+  fn block_at(&self, i: usize, j: usize) -> Option<[(usize, usize); 4]> {
+    if (i + 1 == self.height && self.height % 2 == 1)
+      || (j + 1 == self.width && self.width % 2 == 1)
+    {
+      return None;
+    }
+    let (i, j) = (i as isize, j as isize);
+    Some([
+      self.as_valid_position(i, j)?,
+      self.as_valid_position(i, j + 1)?,
+      self.as_valid_position(i + 1, j)?,
+      self.as_valid_position(i + 1, j + 1)?,
+    ])
+  }
+}
@@ -0,0 +1,80 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::{Neighbourhood, Rule, Topology, World};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorldBuilder {
+  width: usize,
+  height: usize,
+  rule: Rule,
+  topology: Topology,
+  neighbourhood: Neighbourhood,
+  seed: Option<u64>,
+  density: Option<f64>,
+}
+
+impl WorldBuilder {
+  pub fn new() -> Self {
+    Self {
+      // matches the board the simulation runs by default
+      width: 96,
+      height: 96,
+      rule: Rule::default(),
+      topology: Topology::default(),
+      neighbourhood: Neighbourhood::default(),
+      seed: None,
+      density: None,
+    }
+  }
+
+  pub fn size(mut self, width: usize, height: usize) -> Self {
+    self.width = width;
+    self.height = height;
+    self
+  }
+
+  pub fn rule(mut self, rule: Rule) -> Self {
+    self.rule = rule;
+    self
+  }
+
+  pub fn topology(mut self, topology: Topology) -> Self {
+    self.topology = topology;
+    self
+  }
+
+  pub fn neighbourhood(mut self, neighbourhood: Neighbourhood) -> Self {
+    self.neighbourhood = neighbourhood;
+    self
+  }
+
+  pub fn seed(mut self, seed: u64) -> Self {
+    self.seed = Some(seed);
+    self
+  }
+
+  // fraction of cells the random fill attempts to set, clamped to 0..=1
+  pub fn density(mut self, density: f64) -> Self {
+    self.density = Some(density);
+    self
+  }
+
+  // the board is only filled randomly once a seed or density has been given
+  pub fn build(self) -> World {
+    let mut world = World::from_parts(self.width, self.height, self.rule, self.topology);
+    world.set_neighbourhood(self.neighbourhood);
+    if self.seed.is_some() || self.density.is_some() {
+      let seed = self.seed.unwrap_or_else(rand::random);
+      let mut rng = StdRng::seed_from_u64(seed);
+      world.populate(self.density.unwrap_or(0.5), &mut rng);
+    }
+    world
+  }
+}
+
+impl Default for WorldBuilder {
+  fn default() -> Self {
+    Self::new()
+  }
+}
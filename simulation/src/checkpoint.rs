@@ -0,0 +1,34 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::World;
+
+// writes the board as rle every `interval` generations, so long runs can be resumed with --pattern
+pub struct Checkpointer {
+  interval: u64,
+  dir: PathBuf,
+}
+
+impl Checkpointer {
+  pub fn new(interval: u64, dir: impl Into<PathBuf>) -> Self {
+    assert!(interval > 0, "checkpoint interval must be at least 1");
+    Self {
+      interval,
+      dir: dir.into(),
+    }
+  }
+
+  pub fn path_for(&self, generation: u64) -> PathBuf {
+    self.dir.join(format!("checkpoint-{generation:08}.rle"))
+  }
+
+  // returns whether a checkpoint was written for this generation
+  pub fn maybe_checkpoint(&self, world: &World, generation: u64) -> io::Result<bool> {
+    if generation == 0 || !generation.is_multiple_of(self.interval) {
+      return Ok(false);
+    }
+    fs::write(self.path_for(generation), world.to_rle())?;
+    Ok(true)
+  }
+}
@@ -0,0 +1,98 @@
+use std::path::PathBuf;
+
+pub const USAGE: &str =
+  "usage: wasmedge_life [--width N] [--height N] [--generations N] [--seed N] \
+                         [--pattern FILE | --stdin] [--checkpoint-every N] [--checkpoint-dir DIR] \
+                         [--max-population N] [--render] [--fps N] [--ascii]";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+  pub width: usize,
+  pub height: usize,
+  pub generations: u64,
+  // a random seed is picked when none is given
+  pub seed: Option<u64>,
+  // `.rle` or `.cells` file to start from instead of a random board; it sets the board size
+  pub pattern: Option<PathBuf>,
+  // read an `.rle` or `.cells` pattern from stdin; empty input falls back to a random board
+  pub stdin: bool,
+  // write an rle checkpoint every N generations into checkpoint_dir
+  pub checkpoint_every: Option<u64>,
+  pub checkpoint_dir: PathBuf,
+  // stop once a generation ends with more live cells than this
+  pub max_population: Option<usize>,
+  pub render: bool,
+  // cap on generations per second; unpaced when absent
+  pub fps: Option<u32>,
+  // dump the final board as `1`/`0` rows once the run ends
+  pub ascii: bool,
+}
+
+impl Default for Config {
+  fn default() -> Self {
+    Self {
+      width: 96,
+      height: 96,
+      generations: 40,
+      seed: None,
+      pattern: None,
+      stdin: false,
+      checkpoint_every: None,
+      checkpoint_dir: PathBuf::from("."),
+      max_population: None,
+      render: false,
+      fps: None,
+      ascii: false,
+    }
+  }
+}
+
+impl Config {
+  // parses the arguments following the program name
+  pub fn parse<I>(args: I) -> Result<Self, String>
+  where
+    I: IntoIterator<Item = String>,
+  {
+    let mut config = Config::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+      match arg.as_str() {
+        "--width" => config.width = parse_value(&arg, args.next())?,
+        "--height" => config.height = parse_value(&arg, args.next())?,
+        "--generations" => config.generations = parse_value(&arg, args.next())?,
+        "--seed" => config.seed = Some(parse_value(&arg, args.next())?),
+        "--pattern" => config.pattern = Some(parse_value(&arg, args.next())?),
+        "--stdin" => config.stdin = true,
+        "--checkpoint-every" => match parse_value(&arg, args.next())? {
+          0 => return Err(String::from("--checkpoint-every must be at least 1")),
+          interval => config.checkpoint_every = Some(interval),
+        },
+        "--checkpoint-dir" => config.checkpoint_dir = parse_value(&arg, args.next())?,
+        "--max-population" => config.max_population = Some(parse_value(&arg, args.next())?),
+        "--render" => config.render = true,
+        "--fps" => match parse_value(&arg, args.next())? {
+          0 => return Err(String::from("--fps must be at least 1")),
+          fps => config.fps = Some(fps),
+        },
+        "--ascii" => config.ascii = true,
+        _ => return Err(format!("unknown argument '{arg}'")),
+      }
+    }
+    if config.stdin && config.pattern.is_some() {
+      return Err(String::from(
+        "--stdin and --pattern cannot be used together",
+      ));
+    }
+    Ok(config)
+  }
+}
+
+fn parse_value<T>(flag: &str, value: Option<String>) -> Result<T, String>
+where
+  T: std::str::FromStr,
+{
+  let value = value.ok_or_else(|| format!("missing value for {flag}"))?;
+  value
+    .parse()
+    .map_err(|_| format!("invalid value '{value}' for {flag}"))
+}
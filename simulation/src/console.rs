@@ -0,0 +1,72 @@
+use std::io::{self, BufWriter, Stdout, Write};
+
+use crate::{Canvas, OFF_COLOUR, ON_COLOUR};
+
+pub struct ConsoleCanvas {
+  width: usize,
+  height: usize,
+  grid: Vec<u8>,
+  on_glyph: String,
+  off_glyph: String,
+  stdout: Stdout,
+}
+
+impl ConsoleCanvas {
+  pub fn new(width: usize, height: usize) -> Self {
+    Self::with_glyphs(width, height, " @ ", " . ")
+  }
+
+  pub fn with_glyphs(width: usize, height: usize, on_glyph: &str, off_glyph: &str) -> Self {
+    Self {
+      width,
+      height,
+      grid: vec![OFF_COLOUR; width * height],
+      on_glyph: on_glyph.to_string(),
+      off_glyph: off_glyph.to_string(),
+      stdout: io::stdout(),
+    }
+  }
+
+  // the glyph grid render draws, one line per row
+  pub fn render_into(&self, w: &mut impl Write) -> io::Result<()> {
+    for i in 0..self.height {
+      for j in 0..self.width {
+        let repr = match self.grid[i * self.width + j] & 0x1 {
+          ON_COLOUR => &self.on_glyph,
+          OFF_COLOUR => &self.off_glyph,
+          _ => unreachable!(),
+        };
+        w.write_all(repr.as_bytes())?;
+      }
+      w.write_all(b"\n")?;
+    }
+    Ok(())
+  }
+
+  pub fn render_to_string(&self) -> String {
+    let mut buf = Vec::new();
+    // writing into a Vec never fails, and every glyph is a str
+    self.render_into(&mut buf).unwrap();
+    String::from_utf8(buf).unwrap()
+  }
+}
+
+impl Canvas for ConsoleCanvas {
+  type Colour = u8;
+
+  #[inline]
+  fn draw_pixel(&mut self, i: usize, j: usize, colour: Self::Colour) {
+    self.grid[i * self.width + j] = colour;
+  }
+
+  fn render(&self) -> io::Result<()> {
+    let mut buf = BufWriter::new(self.stdout.lock());
+    self.render_into(&mut buf)?;
+    buf.flush()
+  }
+
+  #[inline]
+  fn dimensions(&self) -> Option<(usize, usize)> {
+    Some((self.width, self.height))
+  }
+}
@@ -0,0 +1,39 @@
+use crate::World;
+
+// fixed fills for tests and demos; every cell goes through set_cell, so neighbour counts are
+// consistent from the start
+impl World {
+  // alive where i + j is even, so (0, 0) is alive
+  pub fn checkerboard(width: usize, height: usize) -> Self {
+    Self::filled(width, height, |i, j| (i + j).is_multiple_of(2))
+  }
+
+  // vertical bands `period` columns wide, alternating alive and dead from an alive band at
+  // column 0
+  pub fn stripes(width: usize, height: usize, period: usize) -> Self {
+    assert!(period > 0, "stripe period must be at least 1");
+    Self::filled(width, height, |_, j| (j / period).is_multiple_of(2))
+  }
+
+  // the outermost ring of cells alive, everything inside dead
+  pub fn border(width: usize, height: usize) -> Self {
+    Self::filled(width, height, |i, j| {
+      i == 0 || j == 0 || i + 1 == height || j + 1 == width
+    })
+  }
+
+  fn filled<F>(width: usize, height: usize, alive: F) -> Self
+  where
+    F: Fn(usize, usize) -> bool,
+  {
+    let mut world = World::new(width, height);
+    for i in 0..height {
+      for j in 0..width {
+        if alive(i, j) {
+          world.set_cell(i, j);
+        }
+      }
+    }
+    world
+  }
+}
@@ -0,0 +1,135 @@
+use alloc::format;
+use alloc::string::String;
+use core::fmt;
+
+use crate::{Rule, World};
+
+// a life-like rule where a cell that fails to survive lingers through `states - 2` dying states
+// before it is dead; dying cells are not neighbours and cannot be born into, and a rule with
+// two states is plain life. the three spare cell bits cap `states` at MAX_STATES
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GenerationsRule {
+  pub born: [bool; 9],
+  pub survive: [bool; 9],
+  pub states: u8,
+}
+
+impl GenerationsRule {
+  pub const MIN_STATES: u8 = 2;
+  pub const MAX_STATES: u8 = 9;
+
+  // /2/3
+  pub const BRIANS_BRAIN: GenerationsRule = GenerationsRule {
+    born: [false, false, true, false, false, false, false, false, false],
+    survive: [false; 9],
+    states: 3,
+  };
+
+  // `S/B/C` (`/2/3`) or `B/S/C` (`B2/S/C3`) notation; a rule without a state count is plain life
+  pub fn parse(rulestring: &str) -> Result<Self, String> {
+    let rulestring = rulestring.trim();
+    if rulestring.matches('/').count() < 2 {
+      return Rule::parse(rulestring).map(Self::from);
+    }
+    let (rule, states) = rulestring.rsplit_once('/').unwrap();
+    let digits = states.trim_start_matches(['C', 'c', 'G', 'g']);
+    let states = digits
+      .parse::<u8>()
+      .ok()
+      .filter(|n| (Self::MIN_STATES..=Self::MAX_STATES).contains(n))
+      .ok_or_else(|| format!("invalid state count '{states}' in rulestring"))?;
+    let Rule { born, survive } = Rule::parse(rule)?;
+    Ok(Self {
+      born,
+      survive,
+      states,
+    })
+  }
+
+  // the birth and survival counts alone
+  #[inline]
+  pub fn rule(&self) -> Rule {
+    Rule {
+      born: self.born,
+      survive: self.survive,
+    }
+  }
+}
+
+impl From<Rule> for GenerationsRule {
+  fn from(rule: Rule) -> Self {
+    Self {
+      born: rule.born,
+      survive: rule.survive,
+      states: Self::MIN_STATES,
+    }
+  }
+}
+
+impl fmt::Display for GenerationsRule {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}/C{}", self.rule(), self.states)
+  }
+}
+
+impl World {
+  pub fn with_generations_rule(width: usize, height: usize, rule: GenerationsRule) -> Self {
+    assert!(
+      (GenerationsRule::MIN_STATES..=GenerationsRule::MAX_STATES).contains(&rule.states),
+      "generations rule must have between 2 and 9 states"
+    );
+    let mut world = World::with_rule(width, height, rule.rule());
+    world.states = rule.states;
+    world
+  }
+
+  #[inline]
+  pub fn generations_rule(&self) -> GenerationsRule {
+    GenerationsRule {
+      states: self.states,
+      ..GenerationsRule::from(self.rule)
+    }
+  }
+
+  // 0 for dead, 1 for alive, then 2 up to states - 1 through the dying states; out of range
+  // coordinates read as dead
+  pub fn state(&self, i: usize, j: usize) -> u8 {
+    if !self.in_bounds(i, j) {
+      return 0;
+    }
+    let cell = self.cells[self.index(i, j)];
+    if cell.is_alive() {
+      1
+    } else if cell.is_dying() {
+      cell.decay() + 1
+    } else {
+      0
+    }
+  }
+
+  // moves every dying cell one state on, returning how many did; a cell leaving its last dying
+  // state is dead and can be born into again, so its row needs scanning. only rows flagged in
+  // dying_rows are visited, and a row drops its flag once nothing in it is dying
+  pub(crate) fn decay_dying(&mut self) -> usize {
+    let last = self.states - 2;
+    let mut decayed = 0;
+    for (i, row) in self.cells.chunks_mut(self.width.max(1)).enumerate() {
+      if !self.dying_rows[i] {
+        continue;
+      }
+      let mut still_dying = false;
+      for cell in row.iter_mut().filter(|cell| cell.is_dying()) {
+        if cell.decay() == last {
+          cell.set_decay(0);
+          self.dirty_rows[i] = true;
+        } else {
+          cell.set_decay(cell.decay() + 1);
+          still_dying = true;
+        }
+        decayed += 1;
+      }
+      self.dying_rows[i] = still_dying;
+    }
+    decayed
+  }
+}
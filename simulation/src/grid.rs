@@ -0,0 +1,55 @@
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+
+use crate::World;
+
+// a bool grid whose rows are not all the same length
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShapeError {
+  // 0-based index of the first row that differs from the first row's length
+  pub row: usize,
+  pub expected: usize,
+  pub found: usize,
+}
+
+impl fmt::Display for ShapeError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "row {} has {} cells, expected {}",
+      self.row, self.found, self.expected
+    )
+  }
+}
+
+impl Error for ShapeError {}
+
+impl World {
+  // grid[i][j] is the cell in row i, column j; no rows gives an empty 0x0 board
+  pub fn from_bool_grid(grid: &[Vec<bool>]) -> Result<World, ShapeError> {
+    let width = grid.first().map_or(0, Vec::len);
+    if let Some((row, cells)) = grid.iter().enumerate().find(|(_, r)| r.len() != width) {
+      return Err(ShapeError {
+        row,
+        expected: width,
+        found: cells.len(),
+      });
+    }
+    let mut world = World::new(width, grid.len());
+    for (i, row) in grid.iter().enumerate() {
+      for (j, &alive) in row.iter().enumerate() {
+        if alive {
+          world.set_cell(i, j);
+        }
+      }
+    }
+    Ok(world)
+  }
+
+  pub fn to_bool_grid(&self) -> Vec<Vec<bool>> {
+    (0..self.height)
+      .map(|i| (0..self.width).map(|j| self.get(i, j)).collect())
+      .collect()
+  }
+}
@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+
+use crate::{Rule, World};
+
+type NodeId = usize;
+
+const DEAD: NodeId = 0;
+const ALIVE: NodeId = 1;
+
+// a square of 2^level cells; level 0 nodes are single cells and have no children
+#[derive(Debug, Clone, Copy)]
+struct Node {
+  level: u32,
+  // nw, ne, sw, se
+  children: [NodeId; 4],
+  population: u64,
+}
+
+// quadtree life engine that shares identical subtrees and memoises how each one evolves, so
+// boards with lots of repeated structure can jump 2^k generations at once. it runs on an
+// unbounded plane with the moore neighbourhood whatever the source world's topology and
+// neighbourhood, and births on zero neighbours (B0) cannot be represented and are ignored
+pub struct HashLife {
+  nodes: Vec<Node>,
+  index: HashMap<(u32, [NodeId; 4]), NodeId>,
+  // (node, log2 of the generations advanced) -> the node's centre that many generations on
+  results: HashMap<(NodeId, u32), NodeId>,
+  // empty node of each level, built on demand
+  empty: Vec<NodeId>,
+  root: NodeId,
+  // world coordinates of the root's top left cell
+  origin: (i64, i64),
+  width: usize,
+  height: usize,
+  rule: Rule,
+  generation: u64,
+}
+
+impl HashLife {
+  pub fn from_world(world: &World) -> Self {
+    let leaf = |alive| Node {
+      level: 0,
+      children: [DEAD; 4],
+      population: u64::from(alive),
+    };
+    let mut life = Self {
+      nodes: vec![leaf(false), leaf(true)],
+      index: HashMap::new(),
+      results: HashMap::new(),
+      empty: vec![DEAD],
+      root: DEAD,
+      origin: (0, 0),
+      width: world.width,
+      height: world.height,
+      rule: world.rule,
+      generation: 0,
+    };
+    // the smallest square covering the board, and at least a level 2 node so it can step
+    let side = world.width.max(world.height).max(4);
+    let level = side.next_power_of_two().trailing_zeros();
+    life.root = life.build(world, level, 0, 0);
+    life
+  }
+
+  #[inline]
+  pub fn generation(&self) -> u64 {
+    self.generation
+  }
+
+  #[inline]
+  pub fn population(&self) -> u64 {
+    self.nodes[self.root].population
+  }
+
+  // advances 2^k generations in one go
+  pub fn step_pow2(&mut self, k: u32) {
+    // pad until the pattern sits in the centre quarter of a root big enough to cover 2^k
+    // generations, so nothing it can reach in that time falls outside the result
+    while self.nodes[self.root].level < k + 2 || !self.is_padded(self.root) {
+      self.expand();
+    }
+    self.expand();
+    let level = self.nodes[self.root].level;
+    self.root = self.evolve(self.root, k);
+    let shift = 1i64 << (level - 2);
+    self.origin = (self.origin.0 + shift, self.origin.1 + shift);
+    self.generation += 1 << k;
+  }
+
+  // any number of generations, as a sum of power of two jumps
+  pub fn advance(&mut self, generations: u64) {
+    for k in 0..u64::BITS {
+      if generations & (1 << k) != 0 {
+        self.step_pow2(k);
+      }
+    }
+  }
+
+  // (i, j) of every live cell in world coordinates, which may now lie outside the world
+  pub fn live_cells(&self) -> Vec<(i64, i64)> {
+    let mut cells = Vec::with_capacity(self.population() as usize);
+    self.collect_live(self.root, self.origin, &mut cells);
+    cells
+  }
+
+  // the current board clipped back to the source world's size and rule
+  pub fn to_world(&self) -> World {
+    let mut world = World::with_rule(self.width, self.height, self.rule);
+    for (i, j) in self.live_cells() {
+      if i >= 0 && j >= 0 {
+        world.set(i as usize, j as usize, true);
+      }
+    }
+    world
+  }
+
+  fn build(&mut self, world: &World, level: u32, i: usize, j: usize) -> NodeId {
+    if level == 0 {
+      return if world.get(i, j) { ALIVE } else { DEAD };
+    }
+    let side = 1 << level;
+    if world.population_in(i, j, i + side - 1, j + side - 1) == 0 {
+      return self.empty(level);
+    }
+    let half = side / 2;
+    let nw = self.build(world, level - 1, i, j);
+    let ne = self.build(world, level - 1, i, j + half);
+    let sw = self.build(world, level - 1, i + half, j);
+    let se = self.build(world, level - 1, i + half, j + half);
+    self.join([nw, ne, sw, se])
+  }
+
+  // the canonical node with these four children
+  fn join(&mut self, children: [NodeId; 4]) -> NodeId {
+    let level = self.nodes[children[0]].level + 1;
+    if let Some(&id) = self.index.get(&(level, children)) {
+      return id;
+    }
+    let population = children.iter().map(|&c| self.nodes[c].population).sum();
+    let id = self.nodes.len();
+    self.nodes.push(Node {
+      level,
+      children,
+      population,
+    });
+    self.index.insert((level, children), id);
+    id
+  }
+
+  fn empty(&mut self, level: u32) -> NodeId {
+    while self.empty.len() <= level as usize {
+      let below = *self.empty.last().unwrap();
+      let id = self.join([below; 4]);
+      self.empty.push(id);
+    }
+    self.empty[level as usize]
+  }
+
+  #[inline]
+  fn child(&self, node: NodeId, quadrant: usize) -> NodeId {
+    self.nodes[node].children[quadrant]
+  }
+
+  // the level - 1 node centred on this one
+  fn centre(&mut self, node: NodeId) -> NodeId {
+    let [nw, ne, sw, se] = self.nodes[node].children;
+    self.join([
+      self.child(nw, 3),
+      self.child(ne, 2),
+      self.child(sw, 1),
+      self.child(se, 0),
+    ])
+  }
+
+  // every live cell lies within the centre half
+  fn is_padded(&mut self, node: NodeId) -> bool {
+    let centre = self.centre(node);
+    self.nodes[centre].population == self.nodes[node].population
+  }
+
+  // doubles the root, keeping the board where it was
+  fn expand(&mut self) {
+    let level = self.nodes[self.root].level;
+    let e = self.empty(level - 1);
+    let [nw, ne, sw, se] = self.nodes[self.root].children;
+    let nw = self.join([e, e, e, nw]);
+    let ne = self.join([e, e, ne, e]);
+    let sw = self.join([e, sw, e, e]);
+    let se = self.join([se, e, e, e]);
+    self.root = self.join([nw, ne, sw, se]);
+    let shift = 1i64 << (level - 1);
+    self.origin = (self.origin.0 - shift, self.origin.1 - shift);
+  }
+
+  // the centre of a level >= 2 node, 2^k generations on, for k <= level - 2
+  fn evolve(&mut self, node: NodeId, k: u32) -> NodeId {
+    let level = self.nodes[node].level;
+    if self.nodes[node].population == 0 {
+      return self.empty(level - 1);
+    }
+    if let Some(&result) = self.results.get(&(node, k)) {
+      return result;
+    }
+    let result = if level == 2 {
+      self.evolve_leaf(node)
+    } else {
+      let [nw, ne, sw, se] = self.nodes[node].children;
+      let [_, nw_ne, nw_sw, nw_se] = self.nodes[nw].children;
+      let [ne_nw, _, ne_sw, ne_se] = self.nodes[ne].children;
+      let [sw_nw, sw_ne, _, sw_se] = self.nodes[sw].children;
+      let [se_nw, se_ne, se_sw, _] = self.nodes[se].children;
+      // the nine overlapping level - 1 squares tiling this node
+      let n00 = nw;
+      let n01 = self.join([nw_ne, ne_nw, nw_se, ne_sw]);
+      let n02 = ne;
+      let n10 = self.join([nw_sw, nw_se, sw_nw, sw_ne]);
+      let n11 = self.join([nw_se, ne_sw, sw_ne, se_nw]);
+      let n12 = self.join([ne_sw, ne_se, se_nw, se_ne]);
+      let n20 = sw;
+      let n21 = self.join([sw_ne, se_nw, sw_se, se_sw]);
+      let n22 = se;
+      let squares = [n00, n01, n02, n10, n11, n12, n20, n21, n22];
+      // at full speed both halves of the jump advance, otherwise only the second one does
+      let full_speed = k == level - 2;
+      let mut r = [DEAD; 9];
+      for (r, square) in r.iter_mut().zip(squares) {
+        *r = if full_speed {
+          self.evolve(square, k - 1)
+        } else {
+          self.centre(square)
+        };
+      }
+      let k = if full_speed { k - 1 } else { k };
+      let nw = self.join([r[0], r[1], r[3], r[4]]);
+      let ne = self.join([r[1], r[2], r[4], r[5]]);
+      let sw = self.join([r[3], r[4], r[6], r[7]]);
+      let se = self.join([r[4], r[5], r[7], r[8]]);
+      let nw = self.evolve(nw, k);
+      let ne = self.evolve(ne, k);
+      let sw = self.evolve(sw, k);
+      let se = self.evolve(se, k);
+      self.join([nw, ne, sw, se])
+    };
+    self.results.insert((node, k), result);
+    result
+  }
+
+  // one generation of the inner 2x2 of a 4x4 node, straight from the rule
+  fn evolve_leaf(&mut self, node: NodeId) -> NodeId {
+    let mut grid = [[false; 4]; 4];
+    for (quadrant, &child) in self.nodes[node].children.iter().enumerate() {
+      for (cell, &leaf) in self.nodes[child].children.iter().enumerate() {
+        let (i, j) = ((quadrant / 2) * 2 + cell / 2, (quadrant % 2) * 2 + cell % 2);
+        grid[i][j] = leaf == ALIVE;
+      }
+    }
+    let mut next = [DEAD; 4];
+    for (n, next) in next.iter_mut().enumerate() {
+      let (i, j) = (1 + n / 2, 1 + n % 2);
+      let block = grid[i - 1..=i + 1]
+        .iter()
+        .flat_map(|row| &row[j - 1..=j + 1]);
+      let count = block.filter(|&&alive| alive).count() - usize::from(grid[i][j]);
+      let alive = if grid[i][j] {
+        self.rule.survive[count]
+      } else {
+        // empty nodes are never evolved, so b0 has to be dropped here too to keep results
+        // independent of how the board is tiled
+        count > 0 && self.rule.born[count]
+      };
+      *next = if alive { ALIVE } else { DEAD };
+    }
+    self.join(next)
+  }
+
+  fn collect_live(&self, node: NodeId, (i, j): (i64, i64), cells: &mut Vec<(i64, i64)>) {
+    let Node {
+      level,
+      children,
+      population,
+    } = self.nodes[node];
+    if population == 0 {
+      return;
+    }
+    if level == 0 {
+      cells.push((i, j));
+      return;
+    }
+    let half = 1i64 << (level - 1);
+    self.collect_live(children[0], (i, j), cells);
+    self.collect_live(children[1], (i, j + half), cells);
+    self.collect_live(children[2], (i + half, j), cells);
+    self.collect_live(children[3], (i + half, j + half), cells);
+  }
+}
+
+impl From<&World> for HashLife {
+  fn from(world: &World) -> Self {
+    Self::from_world(world)
+  }
+}
@@ -0,0 +1,84 @@
+use std::io::{self, BufWriter, Stdout, Write};
+
+use crate::{Canvas, NeighbourCount, Rgb, World, ON_COLOUR};
+
+// true colour ramp indexed by neighbour count, from cold (none) to hot (crowded)
+const RAMP: [Rgb; NeighbourCount::MAX as usize + 1] = [
+  (0, 0, 0),
+  (20, 20, 80),
+  (40, 60, 160),
+  (0, 170, 90),
+  (170, 200, 0),
+  (230, 170, 0),
+  (240, 110, 0),
+  (230, 50, 0),
+  (200, 0, 0),
+];
+
+// shades every cell by its live neighbour count and marks live cells on top; counts are only
+// known to the world, so sync it before rendering
+pub struct HeatCanvas {
+  width: usize,
+  height: usize,
+  alive: Vec<bool>,
+  counts: Vec<u8>,
+  stdout: Stdout,
+}
+
+impl HeatCanvas {
+  pub fn new(width: usize, height: usize) -> Self {
+    Self {
+      width,
+      height,
+      alive: vec![false; width * height],
+      counts: vec![0; width * height],
+      stdout: io::stdout(),
+    }
+  }
+
+  pub fn sync(&mut self, world: &World) {
+    for ((alive, count), cell) in self
+      .alive
+      .iter_mut()
+      .zip(&mut self.counts)
+      .zip(&world.cells)
+    {
+      *alive = cell.is_alive();
+      *count = cell.neighbour_count();
+    }
+  }
+
+  // counts past 8 cannot occur and take the hottest shade
+  #[inline]
+  pub fn colour_for_count(count: u8) -> Rgb {
+    RAMP[(count as usize).min(RAMP.len() - 1)]
+  }
+}
+
+impl Canvas for HeatCanvas {
+  type Colour = u8;
+
+  #[inline]
+  fn draw_pixel(&mut self, i: usize, j: usize, colour: Self::Colour) {
+    self.alive[i * self.width + j] = colour & 0x1 == ON_COLOUR;
+  }
+
+  fn render(&self) -> io::Result<()> {
+    let mut buf = BufWriter::new(self.stdout.lock());
+    for i in 0..self.height {
+      for j in 0..self.width {
+        let idx = i * self.width + j;
+        let (r, g, b) = Self::colour_for_count(self.counts[idx]);
+        let glyph = if self.alive[idx] { "@ " } else { "  " };
+        write!(buf, "\x1B[48;2;{r};{g};{b}m{glyph}")?;
+      }
+      buf.write_all(b"\x1B[0m\n")?;
+    }
+    buf.flush()
+  }
+
+  #[inline]
+  fn dimensions(&self) -> Option<(usize, usize)> {
+    Some((self.width, self.height))
+  }
+}
@@ -0,0 +1,59 @@
+use alloc::collections::VecDeque;
+
+use crate::World;
+
+// steps a world while keeping up to `capacity` earlier boards to fall back to; life is not
+// reversible, so undo restores a saved copy rather than running the rule backwards
+pub struct History {
+  world: World,
+  // oldest first
+  past: VecDeque<World>,
+  capacity: usize,
+}
+
+impl History {
+  pub fn new(world: World, capacity: usize) -> Self {
+    Self {
+      world,
+      past: VecDeque::with_capacity(capacity),
+      capacity,
+    }
+  }
+
+  #[inline]
+  pub fn world(&self) -> &World {
+    &self.world
+  }
+
+  // generations that can currently be undone
+  #[inline]
+  pub fn depth(&self) -> usize {
+    self.past.len()
+  }
+
+  // like World::step, saving the current board first and forgetting the oldest once full
+  pub fn step(&mut self) -> usize {
+    if self.capacity > 0 {
+      if self.past.len() == self.capacity {
+        self.past.pop_front();
+      }
+      self.past.push_back(self.world.clone());
+    }
+    self.world.step()
+  }
+
+  // returns false when there is nothing left to undo
+  pub fn undo(&mut self) -> bool {
+    match self.past.pop_back() {
+      Some(previous) => {
+        self.world = previous;
+        true
+      }
+      None => false,
+    }
+  }
+
+  pub fn into_inner(self) -> World {
+    self.world
+  }
+}
@@ -0,0 +1,98 @@
+use std::cell::RefCell;
+use std::io::{self, Write};
+
+use crate::rgb::{OFF_RGB, ON_RGB};
+use crate::{Canvas, Rgb, ON_COLOUR};
+
+// renders each generation as a binary ppm (P6) image into the wrapped writer
+pub struct ImageCanvas<W: Write> {
+  width: usize,
+  height: usize,
+  // side length, in image pixels, of the square drawn for each cell
+  scale: usize,
+  on: Rgb,
+  off: Rgb,
+  pixels: Vec<u8>,
+  writer: RefCell<W>,
+}
+
+impl<W: Write> ImageCanvas<W> {
+  pub fn new(width: usize, height: usize, writer: W) -> Self {
+    Self::with_options(width, height, 1, ON_RGB, OFF_RGB, writer)
+  }
+
+  pub fn with_options(
+    width: usize,
+    height: usize,
+    scale: usize,
+    on: Rgb,
+    off: Rgb,
+    writer: W,
+  ) -> Self {
+    assert!(scale > 0, "image canvas scale must be at least 1");
+    let pixels = [off.0, off.1, off.2].repeat(width * height * scale * scale);
+    Self {
+      width,
+      height,
+      scale,
+      on,
+      off,
+      pixels,
+      writer: RefCell::new(writer),
+    }
+  }
+
+  #[inline]
+  pub fn image_width(&self) -> usize {
+    self.width * self.scale
+  }
+
+  #[inline]
+  pub fn image_height(&self) -> usize {
+    self.height * self.scale
+  }
+
+  pub fn write_ppm(&self, w: &mut impl Write) -> io::Result<()> {
+    write!(
+      w,
+      "P6\n{} {}\n255\n",
+      self.image_width(),
+      self.image_height()
+    )?;
+    w.write_all(&self.pixels)
+  }
+
+  pub fn into_inner(self) -> W {
+    self.writer.into_inner()
+  }
+}
+
+impl<W: Write> Canvas for ImageCanvas<W> {
+  type Colour = u8;
+
+  fn draw_pixel(&mut self, i: usize, j: usize, colour: Self::Colour) {
+    let (r, g, b) = if colour & 0x1 == ON_COLOUR {
+      self.on
+    } else {
+      self.off
+    };
+    let row_len = self.image_width();
+    for y in i * self.scale..(i + 1) * self.scale {
+      for x in j * self.scale..(j + 1) * self.scale {
+        let p = (y * row_len + x) * 3;
+        self.pixels[p..p + 3].copy_from_slice(&[r, g, b]);
+      }
+    }
+  }
+
+  fn render(&self) -> io::Result<()> {
+    let mut writer = self.writer.borrow_mut();
+    self.write_ppm(&mut *writer)?;
+    writer.flush()
+  }
+
+  #[inline]
+  fn dimensions(&self) -> Option<(usize, usize)> {
+    Some((self.width, self.height))
+  }
+}
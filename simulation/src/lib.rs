@@ -0,0 +1,1413 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::collections::hash_map::DefaultHasher;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::hash::Hasher;
+#[cfg(feature = "std")]
+use std::io;
+
+#[cfg(feature = "std")]
+use rand::rngs::StdRng;
+use rand::Rng;
+#[cfg(feature = "std")]
+use rand::SeedableRng;
+
+#[cfg(feature = "std")]
+mod age;
+#[cfg(feature = "std")]
+mod batch;
+#[cfg(feature = "std")]
+mod binary;
+mod block;
+#[cfg(feature = "std")]
+mod builder;
+#[cfg(feature = "std")]
+mod checkpoint;
+#[cfg(feature = "std")]
+mod console;
+mod fixtures;
+mod generations;
+mod grid;
+#[cfg(feature = "std")]
+mod hashlife;
+#[cfg(feature = "std")]
+mod heat;
+mod history;
+#[cfg(feature = "std")]
+mod image;
+#[cfg(feature = "std")]
+mod load;
+mod neighbourhood;
+mod noise;
+mod pattern;
+#[cfg(feature = "std")]
+mod rgb;
+mod rle;
+mod rule;
+#[cfg(feature = "simd")]
+mod simd;
+#[cfg(feature = "serde")]
+mod snapshot;
+#[cfg(feature = "std")]
+mod soup;
+#[cfg(feature = "std")]
+mod sparse;
+#[cfg(feature = "async")]
+mod stream;
+mod symmetry;
+#[cfg(feature = "telemetry")]
+mod telemetry;
+mod text;
+mod topology;
+
+#[cfg(feature = "std")]
+pub use age::AgeCanvas;
+#[cfg(feature = "std")]
+pub use batch::run_batch;
+pub use block::BlockRule;
+#[cfg(feature = "std")]
+pub use builder::WorldBuilder;
+#[cfg(feature = "std")]
+pub use checkpoint::Checkpointer;
+#[cfg(feature = "std")]
+pub use console::ConsoleCanvas;
+pub use generations::GenerationsRule;
+pub use grid::ShapeError;
+#[cfg(feature = "std")]
+pub use hashlife::HashLife;
+#[cfg(feature = "std")]
+pub use heat::HeatCanvas;
+pub use history::History;
+#[cfg(feature = "std")]
+pub use image::ImageCanvas;
+pub use neighbourhood::Neighbourhood;
+pub use pattern::Pattern;
+#[cfg(feature = "std")]
+pub use rgb::{Rgb, RgbCanvas};
+pub use rle::{PatternMeta, RleError};
+pub use rule::Rule;
+#[cfg(feature = "std")]
+pub use soup::{soup_search, SoupResult};
+#[cfg(feature = "std")]
+pub use sparse::SparseWorld;
+pub use symmetry::Symmetry;
+#[cfg(feature = "telemetry")]
+pub use telemetry::JsonlReporter;
+pub use text::ParseError;
+pub use topology::Topology;
+
+pub trait Canvas {
+  type Colour;
+  // (i, j) is row then column, the same way World addresses cells, stored row-major
+  fn draw_pixel(&mut self, i: usize, j: usize, colour: Self::Colour);
+  // provided so the trait asks the same of implementors with or without std; canvases that
+  // never flush anywhere, like NullCanvas, keep the default
+  #[cfg(feature = "std")]
+  fn render(&self) -> io::Result<()> {
+    Ok(())
+  }
+
+  // the whole board at once, alive row-major with width cells per row; backends that can take a
+  // frame in one go override this rather than paying for a call per pixel
+  fn draw_frame(&mut self, alive: &[bool], width: usize, height: usize)
+  where
+    Self::Colour: ProductSingletonCandidate<Self::Colour, Self::Colour>,
+  {
+    for (idx, &alive) in alive.iter().enumerate().take(width * height) {
+      let colour = if alive {
+        Self::Colour::FST
+      } else {
+        Self::Colour::SND
+      };
+      self.draw_pixel(idx / width, idx % width, colour);
+    }
+  }
+
+  // (width, height) in cells, for canvases with a fixed size; next_generation checks it against
+  // the world so a mismatch fails up front instead of as an index panic mid-draw
+  fn dimensions(&self) -> Option<(usize, usize)> {
+    None
+  }
+}
+
+const ON_COLOUR: u8 = 1; // on-cell pixel color
+const OFF_COLOUR: u8 = 0; // off-cell pixel color
+
+// discards everything drawn; for runs that only care about the board itself
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NullCanvas;
+
+impl Canvas for NullCanvas {
+  type Colour = u8;
+
+  #[inline]
+  fn draw_pixel(&mut self, _i: usize, _j: usize, _colour: Self::Colour) {}
+}
+
+pub trait ProductSingletonCandidate<F, S> {
+  const FST: F;
+  const SND: S;
+}
+
+impl ProductSingletonCandidate<Self, Self> for u8 {
+  const FST: Self = ON_COLOUR;
+  const SND: Self = OFF_COLOUR;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NeighbourCount(u8);
+
+impl NeighbourCount {
+  pub const MAX: u8 = 8;
+  pub const MIN: u8 = 0;
+
+  #[inline]
+  pub fn get(self) -> u8 {
+    self.0
+  }
+}
+
+impl TryFrom<u8> for NeighbourCount {
+  type Error = CellError;
+
+  #[inline]
+  fn try_from(byte: u8) -> Result<Self, Self::Error> {
+    match byte {
+      Self::MIN..=Self::MAX => Ok(Self(byte)),
+      _ => Err(CellError::OutOfRange { byte }),
+    }
+  }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Cell(u8);
+
+impl Cell {
+  // dying for seven generations with eight neighbours
+  pub const MAX: u8 = 0b11110000;
+  pub const MIN: u8 = 0;
+
+  #[inline]
+  pub fn is_alive(&self) -> bool {
+    // state is not 0
+    (self.0 & 0x1) != 0
+  }
+
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.0 == 0
+  }
+
+  // the packed byte: bit 0 alive, bits 1-4 neighbour count, bits 5-7 generations spent dying
+  #[inline]
+  pub fn as_byte(&self) -> u8 {
+    self.0
+  }
+
+  // skips the TryFrom checks; the caller must pass a byte that came from as_byte, or one with at
+  // most 8 neighbours that is not both alive and dying, or counts and rules misbehave
+  #[inline]
+  pub const fn from_byte_unchecked(byte: u8) -> Cell {
+    Cell(byte)
+  }
+
+  // a dying cell coming back to life stops dying
+  #[inline]
+  pub fn set_alive(&mut self) {
+    self.0 = (self.0 & 0x1e) | 0x1;
+  }
+
+  #[inline]
+  pub fn set_dead(&mut self) {
+    self.0 &= !0x1;
+  }
+
+  // generations a dead cell has spent dying under a GenerationsRule; 0 once it is fully dead
+  #[inline]
+  pub fn decay(&self) -> u8 {
+    self.0 >> 5
+  }
+
+  #[inline]
+  pub fn is_dying(&self) -> bool {
+    self.decay() != 0
+  }
+
+  #[inline]
+  fn set_decay(&mut self, decay: u8) {
+    self.0 = (self.0 & 0x1f) | (decay << 5);
+  }
+
+  #[inline]
+  pub fn clear_neighbours(&mut self) {
+    self.0 &= !0x1e;
+  }
+
+  #[inline]
+  pub fn neighbours(&self) -> NeighbourCount {
+    let count = (self.0 & 0x1e) >> 1;
+    NeighbourCount::try_from(count).unwrap()
+  }
+
+  #[inline]
+  pub fn neighbour_count(&self) -> u8 {
+    self.neighbours().get()
+  }
+
+  #[inline]
+  pub fn try_increment(&mut self) -> bool {
+    let neighbour_count = self.neighbours().get();
+    if neighbour_count < NeighbourCount::MAX {
+      *self = Self((self.0 & 0xe1) | ((neighbour_count + 1) << 1));
+      true
+    } else {
+      false
+    }
+  }
+
+  #[inline]
+  pub fn try_decrement(&mut self) -> bool {
+    let neighbour_count = self.neighbours().get();
+    if neighbour_count > NeighbourCount::MIN {
+      *self = Self((self.0 & 0xe1) | ((neighbour_count - 1) << 1));
+      true
+    } else {
+      false
+    }
+  }
+}
+
+impl TryFrom<u8> for Cell {
+  type Error = CellError;
+
+  #[inline]
+  fn try_from(byte: u8) -> Result<Self, Self::Error> {
+    let count = (byte & 0x1e) >> 1;
+    if count > NeighbourCount::MAX {
+      Err(CellError::OutOfRange { byte })
+    } else if byte & 0x1 != 0 && byte >> 5 != 0 {
+      // a live cell is never also dying
+      Err(CellError::AliveAndDying { byte })
+    } else {
+      Ok(Self(byte))
+    }
+  }
+}
+
+// why a byte is not a valid NeighbourCount or Cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellError {
+  // more than 8 neighbours
+  OutOfRange { byte: u8 },
+  // the alive bit set alongside decay bits
+  AliveAndDying { byte: u8 },
+}
+
+impl fmt::Display for CellError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      CellError::OutOfRange { byte } => {
+        write!(f, "byte {byte:#010b} holds a neighbour count past 8")
+      }
+      CellError::AliveAndDying { byte } => {
+        write!(f, "byte {byte:#010b} is both alive and dying")
+      }
+    }
+  }
+}
+
+impl Error for CellError {}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+  pub births: usize,
+  pub deaths: usize,
+}
+
+// totals over a whole run_collecting call
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RunReport {
+  pub births: usize,
+  pub deaths: usize,
+  pub peak_population: usize,
+  // first generation the peak was reached, which may be the one the run started from
+  pub peak_generation: u64,
+}
+
+// why a cell will or will not be alive next generation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellOutcome {
+  Survives,
+  // too few neighbours for the rule to keep it alive
+  DiesUnderpopulation,
+  // more neighbours than some count the rule keeps alive
+  DiesOverpopulation,
+  Born,
+  StaysDead,
+}
+
+// how a run_until_stable call ended; generations are the world's own counter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StableOutcome {
+  // the board stopped changing, first reaching its final state at `generation`
+  StillLife { generation: u64 },
+  // the board at `generation` recurred every `period` generations from then on
+  Oscillator { generation: u64, period: u64 },
+  // ran out of steps, stopping at `generation`
+  Unsettled { generation: u64 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorldError {
+  TooLarge {
+    width: usize,
+    height: usize,
+  },
+  // (width, height) of each of the two worlds
+  DimensionMismatch {
+    left: (usize, usize),
+    right: (usize, usize),
+  },
+}
+
+impl fmt::Display for WorldError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      WorldError::TooLarge { width, height } => {
+        write!(f, "a {width}x{height} world is too large to allocate")
+      }
+      WorldError::DimensionMismatch { left, right } => write!(
+        f,
+        "cannot compare a {}x{} world with a {}x{} one",
+        left.0, left.1, right.0, right.1
+      ),
+    }
+  }
+}
+
+impl Error for WorldError {}
+
+#[derive(Clone)]
+pub struct World {
+  cells: Vec<Cell>,
+  // generation each live cell was born on, so its age is read off the generation counter rather
+  // than bumped every step; stale for dead cells
+  born: Vec<u64>,
+  // (i, j, now_alive) for every cell toggled by the latest generation; kept to reuse its allocation
+  changes: Vec<(usize, usize, bool)>,
+  // rows holding a cell whose state or neighbour count changed since the last scan; a row that
+  // is not dirty cannot produce a change, since every cell in it was already decided unchanged
+  dirty_rows: Vec<bool>,
+  // rows that may hold a dying cell under a GenerationsRule, so decay only scans those
+  dying_rows: Vec<bool>,
+  width: usize,
+  height: usize,
+  rule: Rule,
+  // transition_table of the rule, indexed by a cell's low five bits
+  transitions: [Option<bool>; 32],
+  // total cell states under a GenerationsRule, counting alive and dead; 2 is plain life
+  states: u8,
+  topology: Topology,
+  neighbourhood: Neighbourhood,
+  stats: Stats,
+  // generations stepped since the board was made or last reset
+  generation: u64,
+  // whether dead cells with no neighbours are passed over without consulting the rule
+  skip_empty: bool,
+  // times each cell has toggled since tracking started or was last reset; empty when not tracking
+  activity: Vec<u32>,
+}
+
+impl World {
+  pub const MAX_AGE: u8 = u8::MAX;
+
+  // panics if the board cannot be allocated; see try_new. a zero width or height is allowed and
+  // gives an empty board that never changes
+  pub fn new(width: usize, height: usize) -> Self {
+    Self::with_rule(width, height, Rule::default())
+  }
+
+  pub fn try_new(width: usize, height: usize) -> Result<Self, WorldError> {
+    Self::try_from_parts(width, height, Rule::default(), Topology::default())
+  }
+
+  pub fn with_rule(width: usize, height: usize, rule: Rule) -> Self {
+    Self::from_parts(width, height, rule, Topology::default())
+  }
+
+  pub fn with_topology(width: usize, height: usize, topology: Topology) -> Self {
+    Self::from_parts(width, height, Rule::default(), topology)
+  }
+
+  fn from_parts(width: usize, height: usize, rule: Rule, topology: Topology) -> Self {
+    Self::try_from_parts(width, height, rule, topology).unwrap_or_else(|err| panic!("{err}"))
+  }
+
+  // width * height can overflow, or ask for more than the allocator will give, on 32-bit wasi
+  fn try_from_parts(
+    width: usize,
+    height: usize,
+    rule: Rule,
+    topology: Topology,
+  ) -> Result<Self, WorldError> {
+    let too_large = || WorldError::TooLarge { width, height };
+    let cell_count = width.checked_mul(height).ok_or_else(too_large)?;
+    let mut cells = Vec::new();
+    cells
+      .try_reserve_exact(cell_count)
+      .map_err(|_| too_large())?;
+    cells.resize(cell_count, Cell::default());
+    let mut born = Vec::new();
+    born
+      .try_reserve_exact(cell_count)
+      .map_err(|_| too_large())?;
+    born.resize(cell_count, 0);
+    Ok(Self {
+      cells,
+      born,
+      changes: Vec::new(),
+      dirty_rows: vec![true; height],
+      dying_rows: vec![false; height],
+      width,
+      height,
+      rule,
+      transitions: Self::transition_table(&rule),
+      states: 2,
+      topology,
+      neighbourhood: Neighbourhood::default(),
+      stats: Stats::default(),
+      generation: 0,
+      skip_empty: true,
+      activity: Vec::new(),
+    })
+  }
+
+  #[inline]
+  pub fn width(&self) -> usize {
+    self.width
+  }
+
+  #[inline]
+  pub fn height(&self) -> usize {
+    self.height
+  }
+
+  #[inline]
+  pub fn rule(&self) -> &Rule {
+    &self.rule
+  }
+
+  #[inline]
+  pub fn topology(&self) -> Topology {
+    self.topology
+  }
+
+  #[inline]
+  pub fn neighbourhood(&self) -> Neighbourhood {
+    self.neighbourhood
+  }
+
+  // switches which cells count as neighbours and recounts every cell under the new set. rule
+  // entries for counts past the neighbourhood's max_count could never fire, so they are cleared
+  // and rule() reports what the world actually runs; switching back does not restore them
+  pub fn set_neighbourhood(&mut self, neighbourhood: Neighbourhood) {
+    self.neighbourhood = neighbourhood;
+    let max_count = usize::from(neighbourhood.max_count());
+    self.rule.born[max_count + 1..].fill(false);
+    self.rule.survive[max_count + 1..].fill(false);
+    self.transitions = Self::transition_table(&self.rule);
+    self.recount_neighbours();
+  }
+
+  #[inline]
+  pub fn generation(&self) -> u64 {
+    self.generation
+  }
+
+  // live cells keep their ages, so their birth generations move back by as much
+  pub fn reset_generation(&mut self) {
+    for born in &mut self.born {
+      *born = born.wrapping_sub(self.generation);
+    }
+    self.generation = 0;
+  }
+
+  // births and deaths during the most recent generation
+  #[inline]
+  pub fn generation_stats(&self) -> &Stats {
+    &self.stats
+  }
+
+  // (i, j, now_alive) for every cell toggled by the most recent generation, in row-major order
+  #[inline]
+  pub fn last_changes(&self) -> &[(usize, usize, bool)] {
+    &self.changes
+  }
+
+  pub fn population(&self) -> usize {
+    self.cells.iter().filter(|c| c.is_alive()).count()
+  }
+
+  // cells that are alive or have a live neighbour; only these are run through the rule
+  pub fn active_cell_count(&self) -> usize {
+    self.cells.iter().filter(|c| !c.is_empty()).count()
+  }
+
+  // the empty cell skip never changes the outcome; turning it off is for checking exactly that
+  pub fn set_skip_empty(&mut self, skip: bool) {
+    self.skip_empty = skip;
+  }
+
+  // counts how often each cell toggles from here on; turning it on while it is already on keeps
+  // the counts, turning it off drops them
+  pub fn set_track_activity(&mut self, track: bool) {
+    if !track {
+      self.activity = Vec::new();
+    } else if self.activity.is_empty() {
+      self.activity = vec![0; self.cells.len()];
+    }
+  }
+
+  // toggles per cell in row-major order, saturating at u32::MAX; empty unless tracking
+  #[inline]
+  pub fn activity(&self) -> &[u32] {
+    &self.activity
+  }
+
+  pub fn reset_activity(&mut self) {
+    self.activity.fill(0);
+  }
+
+  // live cells in the inclusive rectangle from (i0, j0) to (i1, j1), clamped to the board; either
+  // corner may come first
+  pub fn population_in(&self, i0: usize, j0: usize, i1: usize, j1: usize) -> usize {
+    if self.cells.is_empty() {
+      return 0;
+    }
+    let (top, bottom) = (i0.min(i1), i0.max(i1).min(self.height - 1));
+    let (left, right) = (j0.min(j1), j0.max(j1).min(self.width - 1));
+    if top > bottom || left > right {
+      return 0;
+    }
+    self
+      .cells
+      .chunks(self.width)
+      .skip(top)
+      .take(bottom - top + 1)
+      .map(|row| row[left..=right].iter().filter(|c| c.is_alive()).count())
+      .sum()
+  }
+
+  // a thumbnail with one byte per factor x factor block, 0 for an empty block up to 255 for a full
+  // one, row-major with width.div_ceil(factor) bytes per row; blocks on the right and bottom
+  // edges may be partial and are scaled by the cells they actually cover
+  pub fn downsample(&self, factor: usize) -> Vec<u8> {
+    assert!(factor > 0, "downsample factor must be at least 1");
+    let (rows, cols) = (self.height.div_ceil(factor), self.width.div_ceil(factor));
+    let mut densities = Vec::with_capacity(rows * cols);
+    for r in 0..rows {
+      for c in 0..cols {
+        let (top, left) = (r * factor, c * factor);
+        let (bottom, right) = (
+          (top + factor).min(self.height),
+          (left + factor).min(self.width),
+        );
+        let alive = self.population_in(top, left, bottom - 1, right - 1);
+        let covered = (bottom - top) * (right - left);
+        densities.push((alive * u8::MAX as usize / covered) as u8);
+      }
+    }
+    densities
+  }
+
+  // whether (i, j), row then column, lies on the board
+  #[inline]
+  pub fn in_bounds(&self, i: usize, j: usize) -> bool {
+    i < self.height && j < self.width
+  }
+
+  // out of range coordinates read as dead
+  #[inline]
+  pub fn get(&self, i: usize, j: usize) -> bool {
+    self.in_bounds(i, j) && self.cells[self.index(i, j)].is_alive()
+  }
+
+  // out of range coordinates are ignored, matching how patterns clip at the edge
+  pub fn set(&mut self, i: usize, j: usize, alive: bool) {
+    if !self.in_bounds(i, j) {
+      return;
+    }
+    if alive {
+      self.set_cell(i, j);
+    } else {
+      self.clear_cell(i, j);
+    }
+  }
+
+  pub fn toggle(&mut self, i: usize, j: usize) {
+    self.set(i, j, !self.get(i, j));
+  }
+
+  #[inline]
+  pub fn cells(&self) -> &[Cell] {
+    &self.cells
+  }
+
+  // raw access for importers; neighbour counts are not maintained, so call
+  // recount_neighbours once the alive bits are in place
+  #[inline]
+  pub fn cells_mut(&mut self) -> &mut [Cell] {
+    self.dirty_rows.fill(true);
+    self.dying_rows.fill(true);
+    &mut self.cells
+  }
+
+  // rebuilds every neighbour count from the alive bits alone
+  pub fn recount_neighbours(&mut self) {
+    for cell in &mut self.cells {
+      cell.clear_neighbours();
+    }
+    self.dirty_rows.fill(true);
+    for idx in 0..self.cells.len() {
+      if self.cells[idx].is_alive() {
+        self.increment_neighbours(idx / self.width, idx % self.width);
+      }
+    }
+  }
+
+  // shifts every live cell, keeping its age; cells pushed past the edge are dropped, or wrap
+  // under a toroidal topology. dying cells are cleared
+  pub fn translate(&mut self, di: isize, dj: isize) {
+    let moved: Vec<((usize, usize), u64)> = self
+      .live_cells()
+      .filter_map(|(i, j)| {
+        let to = self.as_valid_position(i as isize + di, j as isize + dj)?;
+        Some((to, self.born[self.index(i, j)]))
+      })
+      .collect();
+    self.cells.fill(Cell::default());
+    self.dirty_rows.fill(true);
+    for ((i, j), born) in moved {
+      self.set_cell(i, j);
+      let idx = self.index(i, j);
+      self.born[idx] = born;
+    }
+  }
+
+  // generations a live cell has survived, saturating at MAX_AGE; dead and out of range cells
+  // read as 0
+  #[inline]
+  pub fn age(&self, i: usize, j: usize) -> u8 {
+    if self.in_bounds(i, j) {
+      self.age_at(self.index(i, j))
+    } else {
+      0
+    }
+  }
+
+  // the age of every cell, row-major
+  pub fn ages(&self) -> impl Iterator<Item = u8> + '_ {
+    (0..self.cells.len()).map(|idx| self.age_at(idx))
+  }
+
+  #[inline]
+  fn age_at(&self, idx: usize) -> u8 {
+    if !self.cells[idx].is_alive() {
+      return 0;
+    }
+    let age = self.generation.wrapping_sub(self.born[idx]);
+    age.min(u64::from(Self::MAX_AGE)) as u8
+  }
+
+  // (i, j) of every live cell, in row-major order
+  pub fn live_cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let width = self.width;
+    self
+      .cells
+      .iter()
+      .enumerate()
+      .filter(|(_, c)| c.is_alive())
+      .map(move |(idx, _)| (idx / width, idx % width))
+  }
+
+  // (min_i, min_j, max_i, max_j) of the tightest box holding every live cell, none on an empty board
+  pub fn bounding_box(&self) -> Option<(usize, usize, usize, usize)> {
+    self.live_cells().fold(None, |bounds, (i, j)| match bounds {
+      None => Some((i, j, i, j)),
+      Some((min_i, min_j, max_i, max_j)) => {
+        Some((min_i.min(i), min_j.min(j), max_i.max(i), max_j.max(j)))
+      }
+    })
+  }
+
+  pub fn random<R>(width: usize, height: usize, rng: &mut R) -> Self
+  where
+    R: Rng,
+  {
+    let mut world = World::new(width, height);
+    world.populate(0.5, rng);
+    world
+  }
+
+  // each cell is alive with probability density_fn(i, j); values outside 0..=1 act as 0 or 1
+  pub fn random_with<R, F>(width: usize, height: usize, rng: &mut R, density_fn: F) -> Self
+  where
+    R: Rng,
+    F: Fn(usize, usize) -> f64,
+  {
+    let mut world = World::new(width, height);
+    for i in 0..height {
+      for j in 0..width {
+        if rng.gen::<f64>() < density_fn(i, j) {
+          world.set_cell(i, j);
+        }
+      }
+    }
+    world
+  }
+
+  // exactly alive_count distinct live cells, drawn by a partial fisher-yates shuffle of the
+  // cell indices; random and populate may pick a cell twice and land below their density
+  pub fn random_exact<R>(width: usize, height: usize, rng: &mut R, alive_count: usize) -> Self
+  where
+    R: Rng,
+  {
+    let mut world = World::new(width, height);
+    let len = world.cells.len();
+    assert!(
+      alive_count <= len,
+      "cannot place {alive_count} live cells on a {width}x{height} board"
+    );
+    let mut indices: Vec<usize> = (0..len).collect();
+    for k in 0..alive_count {
+      indices.swap(k, rng.gen_range(k..len));
+      let idx = indices[k];
+      world.set_cell(idx / width, idx % width);
+    }
+    world
+  }
+
+  // same seed and dimensions always produce the same board
+  #[cfg(feature = "std")]
+  pub fn random_from_seed(width: usize, height: usize, seed: u64) -> Self {
+    let mut rng = StdRng::seed_from_u64(seed);
+    Self::random(width, height, &mut rng)
+  }
+
+  #[cfg(feature = "std")]
+  pub fn builder() -> WorldBuilder {
+    WorldBuilder::new()
+  }
+
+  // back to an all dead board at generation 0, reusing every buffer; size, rule and topology stay
+  pub fn reset(&mut self) {
+    self.cells.fill(Cell::default());
+    self.changes.clear();
+    self.dirty_rows.fill(true);
+    self.stats = Stats::default();
+    self.generation = 0;
+    self.activity.fill(0);
+  }
+
+  // reset, then filled the way World::random fills a new board
+  pub fn reset_random<R>(&mut self, rng: &mut R)
+  where
+    R: Rng,
+  {
+    self.reset();
+    self.populate(0.5, rng);
+  }
+
+  // scatters cells_len * density random picks; repeated picks mean the result is slightly sparser
+  pub fn populate<R>(&mut self, density: f64, rng: &mut R)
+  where
+    R: Rng,
+  {
+    // no cell to pick, and gen_range panics on an empty range
+    if self.cells.is_empty() {
+      return;
+    }
+    let init_length = (self.cells.len() as f64 * density.clamp(0.0, 1.0)) as usize;
+    for _ in 0..init_length {
+      let i = rng.gen_range(0..self.height);
+      let j = rng.gen_range(0..self.width);
+      self.set_cell(i, j);
+    }
+  }
+
+  // advances one generation, returning the number of cells that toggled; under a GenerationsRule
+  // cells moving through their dying states count too, though only toggles are drawn
+  pub fn next_generation<Co, Ca>(&mut self, canvas: &mut Ca) -> usize
+  where
+    Co: ProductSingletonCandidate<Co, Co>,
+    Ca: Canvas<Colour = Co>,
+  {
+    self.check_canvas(canvas);
+    self.step_with(|i, j, alive| {
+      canvas.draw_pixel(i, j, if alive { Co::FST } else { Co::SND });
+    })
+  }
+
+  // draws the rows x cols window whose top left cell is (top, left) onto a canvas of that size,
+  // cell (top + r, left + c) at (r, c); the parts of the window past the board draw as off
+  pub fn render_window<Co, Ca>(
+    &self,
+    canvas: &mut Ca,
+    top: usize,
+    left: usize,
+    rows: usize,
+    cols: usize,
+  ) where
+    Co: ProductSingletonCandidate<Co, Co>,
+    Ca: Canvas<Colour = Co>,
+  {
+    if let Some((width, height)) = canvas.dimensions() {
+      assert!(
+        (width, height) == (cols, rows),
+        "canvas is {width}x{height} but the window is {cols}x{rows}"
+      );
+    }
+    for r in 0..rows {
+      for c in 0..cols {
+        let alive = top
+          .checked_add(r)
+          .zip(left.checked_add(c))
+          .is_some_and(|(i, j)| i < self.height && j < self.width && self.get(i, j));
+        canvas.draw_pixel(r, c, if alive { Co::FST } else { Co::SND });
+      }
+    }
+  }
+
+  // hands the canvas every cell of the current board in a single draw_frame call
+  pub fn draw<Co, Ca>(&self, canvas: &mut Ca)
+  where
+    Co: ProductSingletonCandidate<Co, Co>,
+    Ca: Canvas<Colour = Co>,
+  {
+    self.check_canvas(canvas);
+    let alive: Vec<bool> = self.cells.iter().map(Cell::is_alive).collect();
+    canvas.draw_frame(&alive, self.width, self.height);
+  }
+
+  // like next_generation, but redraws the whole board once through draw_frame instead of one
+  // draw_pixel per toggled cell
+  pub fn next_generation_frame<Co, Ca>(&mut self, canvas: &mut Ca) -> usize
+  where
+    Co: ProductSingletonCandidate<Co, Co>,
+    Ca: Canvas<Colour = Co>,
+  {
+    self.check_canvas(canvas);
+    let changed = self.step();
+    self.draw(canvas);
+    changed
+  }
+
+  // a canvas that reports its size must match the board it draws
+  pub(crate) fn check_canvas<Ca: Canvas>(&self, canvas: &Ca) {
+    if let Some((width, height)) = canvas.dimensions() {
+      assert!(
+        (width, height) == (self.width, self.height),
+        "canvas is {width}x{height} but the world is {}x{}",
+        self.width,
+        self.height
+      );
+    }
+  }
+
+  // advances one generation without drawing; a return of 0 means the board is a still life
+  pub fn step(&mut self) -> usize {
+    self.step_with(|_, _, _| {})
+  }
+
+  // like step, but first pads the board on every side whenever a live cell touches the border,
+  // roughly doubling it each time until it reaches max_width x max_height
+  pub fn step_growing(&mut self, max_width: usize, max_height: usize) -> usize {
+    if self.touches_border() {
+      let width = (self.width + 2 * (self.width / 2).max(4)).min(max_width.max(self.width));
+      let height = (self.height + 2 * (self.height / 2).max(4)).min(max_height.max(self.height));
+      let offset = ((height - self.height) / 2, (width - self.width) / 2);
+      if offset != (0, 0) {
+        self.resize(width, height, offset.0 as isize, offset.1 as isize);
+      }
+    }
+    self.step()
+  }
+
+  fn touches_border(&self) -> bool {
+    if self.cells.is_empty() {
+      return false;
+    }
+    let (bottom, right) = (self.height - 1, self.width - 1);
+    self.population_in(0, 0, 0, right) > 0
+      || self.population_in(bottom, 0, bottom, right) > 0
+      || self.population_in(0, 0, bottom, 0) > 0
+      || self.population_in(0, right, bottom, right) > 0
+  }
+
+  // moves the board onto fresh new_width x new_height buffers with its old (0, 0) at (offset_i,
+  // offset_j), keeping each cell's state, age and activity and recounting neighbours; cells that
+  // land off the new board are dropped, so negative offsets crop the top and left
+  pub fn resize(&mut self, new_width: usize, new_height: usize, offset_i: isize, offset_j: isize) {
+    let mut world = World::from_parts(new_width, new_height, self.rule, self.topology);
+    world.neighbourhood = self.neighbourhood;
+    world.set_track_activity(!self.activity.is_empty());
+    for (idx, (&cell, &born)) in self.cells.iter().zip(&self.born).enumerate() {
+      let i = (idx / self.width) as isize + offset_i;
+      let j = (idx % self.width) as isize + offset_j;
+      if i >= 0 && j >= 0 && world.in_bounds(i as usize, j as usize) {
+        let (i, j) = (i as usize, j as usize);
+        let mut cell = cell;
+        cell.clear_neighbours();
+        let to = world.index(i, j);
+        world.cells[to] = cell;
+        world.born[to] = born;
+        if cell.is_dying() {
+          world.dying_rows[i] = true;
+        }
+        if let Some(&count) = self.activity.get(idx) {
+          world.activity[to] = count;
+        }
+      }
+    }
+    world.recount_neighbours();
+    world.states = self.states;
+    world.generation = self.generation;
+    world.skip_empty = self.skip_empty;
+    *self = world;
+  }
+
+  // the board one generation on, leaving this one untouched
+  pub fn peek_next(&self) -> World {
+    let mut next = self.clone();
+    next.step();
+    next
+  }
+
+  // the outcome of every cell next generation in row-major order, read off the current
+  // neighbour counts; dying cells under a GenerationsRule stay dead
+  pub fn explain_next(&self) -> Vec<CellOutcome> {
+    self
+      .cells
+      .iter()
+      .map(|&cell| match (cell.is_alive(), self.transition(cell)) {
+        (true, Some(false)) => {
+          let count = cell.neighbour_count() as usize;
+          if self.rule.survive[..count].contains(&true) {
+            CellOutcome::DiesOverpopulation
+          } else {
+            CellOutcome::DiesUnderpopulation
+          }
+        }
+        (true, _) => CellOutcome::Survives,
+        (false, Some(true)) => CellOutcome::Born,
+        (false, _) => CellOutcome::StaysDead,
+      })
+      .collect()
+  }
+
+  // steps `generations` times, handing the hook the board and its 1-based generation after each
+  pub fn run<F>(&mut self, generations: u64, mut hook: F)
+  where
+    F: FnMut(&World, u64),
+  {
+    for generation in 1..=generations {
+      self.step();
+      hook(self, generation);
+    }
+  }
+
+  // endless iterator stepping the board in place on every next, yielding the generation reached;
+  // bound it with take or take_while
+  pub fn generations(&mut self) -> impl Iterator<Item = u64> + '_ {
+    core::iter::from_fn(move || {
+      self.step();
+      Some(self.generation)
+    })
+  }
+
+  // steps `generations` times, totalling the per-generation stats; population is tracked from
+  // the births and deaths rather than recounted
+  pub fn run_collecting(&mut self, generations: u64) -> RunReport {
+    let mut population = self.population();
+    let mut report = RunReport {
+      peak_population: population,
+      peak_generation: self.generation,
+      ..RunReport::default()
+    };
+    for _ in 0..generations {
+      self.step();
+      report.births += self.stats.births;
+      report.deaths += self.stats.deaths;
+      population = population + self.stats.births - self.stats.deaths;
+      if population > report.peak_population {
+        report.peak_population = population;
+        report.peak_generation = self.generation;
+      }
+    }
+    report
+  }
+
+  // like step repeated `generations` times, but stops after the first generation whose population
+  // exceeds max_population and returns that generation
+  pub fn run_capped(&mut self, generations: u64, max_population: usize) -> Option<u64> {
+    for generation in 1..=generations {
+      self.step();
+      if self.population() > max_population {
+        return Some(generation);
+      }
+    }
+    None
+  }
+
+  // advances one generation, calling on_change with (i, j, now_alive) for each toggled cell in
+  // row-major order as the change is applied, and returns the same count as next_generation.
+  // every decision is made against the untouched board before any change is applied, so the
+  // neighbour counts updated by set_cell/clear_cell never leak into the current generation
+  pub fn step_with<F>(&mut self, mut on_change: F) -> usize
+  where
+    F: FnMut(usize, usize, bool),
+  {
+    self.generation += 1;
+    let mut changes = core::mem::take(&mut self.changes);
+    changes.clear();
+    if self.cells.is_empty() {
+      self.changes = changes;
+      self.stats = Stats::default();
+      return 0;
+    }
+    self.collect_changes(&mut changes);
+    // applying the changes below marks the rows worth scanning next generation
+    self.dirty_rows.fill(false);
+    // cells already dying move on before this generation's deaths join them
+    let decayed = if self.states > 2 {
+      self.decay_dying()
+    } else {
+      0
+    };
+    self.stats = Stats::default();
+    for &(i, j, alive) in &changes {
+      self.apply_change(i, j, alive, &mut on_change);
+    }
+    self.changes = changes;
+    self.stats.births + self.stats.deaths + decayed
+  }
+
+  #[cfg(not(feature = "rayon"))]
+  fn collect_changes(&self, changes: &mut Vec<(usize, usize, bool)>) {
+    for (i, row) in self.cells.chunks(self.width.max(1)).enumerate() {
+      if self.dirty_rows[i] {
+        self.row_changes(i, row, changes);
+      }
+    }
+  }
+
+  // rows are decided in parallel; collecting keeps them in the same order as the serial scan
+  #[cfg(feature = "rayon")]
+  fn collect_changes(&self, changes: &mut Vec<(usize, usize, bool)>) {
+    use rayon::prelude::*;
+
+    changes.par_extend(
+      self
+        .cells
+        .par_chunks(self.width.max(1))
+        .enumerate()
+        .filter(|&(i, _)| self.dirty_rows[i])
+        .flat_map_iter(|(i, row)| {
+          let mut row_changes = Vec::new();
+          self.row_changes(i, row, &mut row_changes);
+          row_changes
+        }),
+    );
+  }
+
+  #[cfg(not(feature = "simd"))]
+  fn row_changes(&self, i: usize, row: &[Cell], changes: &mut Vec<(usize, usize, bool)>) {
+    for (j, &cell) in row.iter().enumerate() {
+      if let Some(alive) = self.transition(cell) {
+        changes.push((i, j, alive));
+      }
+    }
+  }
+
+  // the state a cell moves to under the rule, if it changes at all
+  #[inline]
+  fn transition(&self, cell: Cell) -> Option<bool> {
+    // skim past off cells with no neighbours, unless the rule births them (B0)
+    if self.skip_empty && cell.is_empty() && !self.rule.born[0] {
+      return None;
+    }
+    // dying cells run out their states whatever their neighbours do
+    if cell.is_dying() {
+      return None;
+    }
+    self.transitions[(cell.0 & 0x1f) as usize]
+  }
+
+  // what transition returns for each (alive | neighbours << 1) state, so deciding a cell is a
+  // lookup rather than a branch on the rule; counts past 8 cannot occur and never change
+  fn transition_table(rule: &Rule) -> [Option<bool>; 32] {
+    core::array::from_fn(|state| {
+      let count = state >> 1;
+      if count > NeighbourCount::MAX as usize {
+        None
+      } else if state & 0x1 != 0 {
+        // cell active; turn off if the rule doesnt let it survive
+        (!rule.survive[count]).then_some(false)
+      } else {
+        // cell inactive; turn on if the rule births it
+        rule.born[count].then_some(true)
+      }
+    })
+  }
+
+  #[inline]
+  fn apply_change<F>(&mut self, i: usize, j: usize, alive: bool, on_change: &mut F)
+  where
+    F: FnMut(usize, usize, bool),
+  {
+    let idx = self.index(i, j);
+    if alive {
+      self.set_cell(i, j);
+      self.stats.births += 1;
+    } else {
+      self.clear_cell(i, j);
+      if self.states > 2 {
+        self.cells[idx].set_decay(1);
+        self.dying_rows[i] = true;
+      }
+      self.stats.deaths += 1;
+    }
+    if let Some(count) = self.activity.get_mut(idx) {
+      *count = count.saturating_add(1);
+    }
+    on_change(i, j, alive);
+  }
+
+  // (i, j) of every cell alive in one world but not the other, in row-major order
+  pub fn diff(&self, other: &World) -> Result<Vec<(usize, usize)>, WorldError> {
+    if (self.width, self.height) != (other.width, other.height) {
+      return Err(WorldError::DimensionMismatch {
+        left: (self.width, self.height),
+        right: (other.width, other.height),
+      });
+    }
+    Ok(
+      self
+        .cells
+        .iter()
+        .zip(&other.cells)
+        .enumerate()
+        .filter(|(_, (a, b))| a.is_alive() != b.is_alive())
+        .map(|(idx, _)| (idx / self.width, idx % self.width))
+        .collect(),
+    )
+  }
+
+  // steps until a previously seen board recurs, returning the period of the cycle
+  #[cfg(feature = "std")]
+  pub fn detect_cycle(&mut self, max_steps: usize) -> Option<usize> {
+    let mut seen = HashMap::new();
+    seen.insert(self.alive_hash(), 0);
+    for step in 1..=max_steps {
+      self.step();
+      if let Some(first_seen) = seen.insert(self.alive_hash(), step) {
+        return Some(step - first_seen);
+      }
+    }
+    None
+  }
+
+  // steps up to max times until the board stops changing or repeats an earlier board; an empty
+  // board counts as a still life
+  #[cfg(feature = "std")]
+  pub fn run_until_stable(&mut self, max: u64) -> StableOutcome {
+    let mut seen = HashMap::new();
+    seen.insert(self.alive_hash(), self.generation);
+    for _ in 0..max {
+      if self.step() == 0 {
+        return StableOutcome::StillLife {
+          generation: self.generation - 1,
+        };
+      }
+      if let Some(first_seen) = seen.insert(self.alive_hash(), self.generation) {
+        return StableOutcome::Oscillator {
+          generation: first_seen,
+          period: self.generation - first_seen,
+        };
+      }
+    }
+    StableOutcome::Unsettled {
+      generation: self.generation,
+    }
+  }
+
+  // hashes only the alive bits, so boards that differ in neighbour counts alone hash the same
+  #[cfg(feature = "std")]
+  fn alive_hash(&self) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    hasher.write(&self.alive_bitmap());
+    hasher.finish()
+  }
+
+  // alive bits packed 8 cells per byte, least significant bit first
+  #[cfg(feature = "std")]
+  fn alive_bitmap(&self) -> Vec<u8> {
+    self
+      .cells
+      .chunks(8)
+      .map(|chunk| {
+        chunk
+          .iter()
+          .enumerate()
+          .fold(0u8, |byte, (n, c)| byte | (u8::from(c.is_alive()) << n))
+      })
+      .collect()
+  }
+
+  // inverse of alive_bitmap; the neighbour counts are rebuilt through set_cell
+  #[cfg(feature = "std")]
+  fn set_alive_bitmap(&mut self, bitmap: &[u8]) {
+    for idx in 0..self.cells.len() {
+      if bitmap[idx / 8] & (1 << (idx % 8)) != 0 {
+        self.set_cell(idx / self.width, idx % self.width);
+      }
+    }
+  }
+
+  // no-op on a live cell, so its neighbours are never counted twice
+  fn set_cell(&mut self, i: usize, j: usize) {
+    let cell_ptr = self.index(i, j);
+    if self.cells[cell_ptr].is_alive() {
+      return;
+    }
+    // cell is alive
+    self.cells[cell_ptr].set_alive();
+    self.born[cell_ptr] = self.generation;
+    self.dirty_rows[i] = true;
+    self.increment_neighbours(i, j);
+  }
+
+  // no-op on a dead cell, so its neighbours are never decremented twice
+  fn clear_cell(&mut self, i: usize, j: usize) {
+    let cell_ptr = self.index(i, j);
+    if !self.cells[cell_ptr].is_alive() {
+      return;
+    }
+    // cell is dead
+    self.cells[cell_ptr].set_dead();
+    self.dirty_rows[i] = true;
+    self.decrement_neighbours(i, j);
+  }
+
+  fn increment_neighbours(&mut self, i: usize, j: usize) {
+    if let Some(neighbours) = self.interior_neighbours(i, j) {
+      for idx in neighbours {
+        self.cells[idx].try_increment();
+      }
+      self.dirty_rows[i - 1..=i + 1].fill(true);
+      return;
+    }
+    for &(i_offset, j_offset) in self.neighbourhood.offsets() {
+      // update neighbours in place; step_with decides every change before applying any of them
+      if let Some((i, j)) = self.as_valid_position(i as isize + i_offset, j as isize + j_offset) {
+        let idx = self.index(i, j);
+        self.cells[idx].try_increment();
+        self.dirty_rows[i] = true;
+      }
+    }
+  }
+
+  fn decrement_neighbours(&mut self, i: usize, j: usize) {
+    if let Some(neighbours) = self.interior_neighbours(i, j) {
+      for idx in neighbours {
+        self.cells[idx].try_decrement();
+      }
+      self.dirty_rows[i - 1..=i + 1].fill(true);
+      return;
+    }
+    for &(i_offset, j_offset) in self.neighbourhood.offsets() {
+      // update neighbours
+      if let Some((i, j)) = self.as_valid_position(i as isize + i_offset, j as isize + j_offset) {
+        let idx = self.index(i, j);
+        self.cells[idx].try_decrement();
+        self.dirty_rows[i] = true;
+      }
+    }
+  }
+
+  // indices of the eight moore neighbours of an interior cell, which every topology agrees on
+  #[inline]
+  fn interior_neighbours(&self, i: usize, j: usize) -> Option<[usize; 8]> {
+    let w = self.width;
+    if self.neighbourhood != Neighbourhood::Moore
+      || i == 0
+      || j == 0
+      || i + 1 >= self.height
+      || j + 1 >= w
+    {
+      return None;
+    }
+    let idx = self.index(i, j);
+    Some([
+      idx - w - 1,
+      idx - w,
+      idx - w + 1,
+      idx - 1,
+      idx + 1,
+      idx + w - 1,
+      idx + w,
+      idx + w + 1,
+    ])
+  }
+
+  // row-major position of (i, j) in cells and born; (i, j) must be in bounds
+  #[inline]
+  pub(crate) fn index(&self, i: usize, j: usize) -> usize {
+    debug_assert!(self.in_bounds(i, j), "({i}, {j}) is off the board");
+    i * self.width + j
+  }
+
+  #[inline]
+  fn as_valid_position(&self, neighbour_i: isize, neighbour_j: isize) -> Option<(usize, usize)> {
+    self
+      .topology
+      .resolve(neighbour_i, neighbour_j, self.height, self.width)
+  }
+}
+
+// boards are equal when they have the same size and the same live cells; neighbour counts follow
+// from those, and rule, ages and statistics are not part of the board
+impl PartialEq for World {
+  fn eq(&self, other: &Self) -> bool {
+    self.width == other.width
+      && self.height == other.height
+      && self
+        .cells
+        .iter()
+        .zip(&other.cells)
+        .all(|(a, b)| a.is_alive() == b.is_alive())
+  }
+}
+
+impl Eq for World {}
+
+// the board itself goes through Display, so failed assert_eq!s show the two boards as text
+impl fmt::Debug for World {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("World")
+      .field("width", &self.width)
+      .field("height", &self.height)
+      .field("generation", &self.generation)
+      .field("board", &format_args!("\n{self}"))
+      .finish()
+  }
+}
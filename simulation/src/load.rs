@@ -0,0 +1,47 @@
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::World;
+
+impl World {
+  // picks the parser from the extension, `.rle` or `.cells`; under wasi the file must sit in a
+  // preopened directory, otherwise the read fails like any other io error
+  pub fn from_path(path: &Path) -> io::Result<World> {
+    let extension = path.extension().and_then(|ext| ext.to_str());
+    if !matches!(extension, Some("rle" | "cells")) {
+      return Err(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("unsupported pattern file '{}'", path.display()),
+      ));
+    }
+    let contents = fs::read_to_string(path)?;
+    match extension {
+      Some("rle") => World::from_rle(&contents).map_err(invalid_data),
+      _ => World::from_cells(&contents).map_err(invalid_data),
+    }
+  }
+
+  // for input without a file name, such as stdin: rle when the first line past the `#` comments
+  // is an `x = ...` header, `.cells` otherwise
+  pub fn from_pattern_str(contents: &str) -> io::Result<World> {
+    let is_rle = contents
+      .lines()
+      .map(str::trim_start)
+      .find(|line| !line.is_empty() && !line.starts_with('#'))
+      .is_some_and(|line| line.starts_with('x'));
+    if is_rle {
+      World::from_rle(contents).map_err(invalid_data)
+    } else {
+      World::from_cells(contents).map_err(invalid_data)
+    }
+  }
+}
+
+fn invalid_data<E>(err: E) -> io::Error
+where
+  E: Error + Send + Sync + 'static,
+{
+  io::Error::new(io::ErrorKind::InvalidData, err)
+}
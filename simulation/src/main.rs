@@ -1,308 +1,133 @@
-use std::io::{self, Stdout};
+use std::io::{self, Read};
+use std::time::{Duration, Instant};
+use std::{env, process, thread};
 
 use rand::rngs::StdRng;
-use rand::Rng;
 use rand::SeedableRng;
 
-pub trait Canvas {
-  type Colour;
-  fn draw_pixel(&mut self, i: usize, j: usize, colour: Self::Colour);
-  fn render(&self);
-}
-
-pub struct ConsoleCanvas {
-  width: usize,
-  height: usize,
-  grid: Vec<u8>,
-  stdout: Stdout,
-}
-
-impl ConsoleCanvas {
-  pub fn new(width: usize, height: usize) -> Self {
-    Self {
-      width,
-      height,
-      grid: vec![OFF_COLOUR; width * height],
-      stdout: io::stdout(),
-    }
-  }
-}
+use simulation::{Canvas, Checkpointer, ConsoleCanvas, World};
 
-const ON_COLOUR: u8 = 1; // on-cell pixel color
-const OFF_COLOUR: u8 = 0; // off-cell pixel color
+mod config;
 
-impl Canvas for ConsoleCanvas {
-  type Colour = u8;
+use config::Config;
 
-  #[inline]
-  fn draw_pixel(&mut self, i: usize, j: usize, colour: Self::Colour) {
-    self.grid[i * self.width + j] = colour;
-  }
-
-  fn render(&self) {
-    use std::io::Write;
-    let lock = self.stdout.lock();
-    let mut buf = std::io::BufWriter::new(lock);
-    for i in 0..self.height {
-      for j in 0..self.width {
-        let repr = match self.grid[i * self.width + j] & 0x1 {
-          ON_COLOUR => b" @ ",
-          OFF_COLOUR => b" . ",
-          _ => unreachable!(),
-        };
-        let _ = buf.write(repr);
+fn main() {
+  let config = match Config::parse(env::args().skip(1)) {
+    Ok(config) => config,
+    Err(err) => {
+      eprintln!("error: {err}");
+      eprintln!("{}", config::USAGE);
+      process::exit(2);
+    }
+  };
+  let mut current_map = match &config.pattern {
+    Some(path) => World::from_path(path).unwrap_or_else(|err| {
+      eprintln!("error: cannot load {}: {err}", path.display());
+      process::exit(1);
+    }),
+    None => {
+      let piped = if config.stdin {
+        read_pattern(io::stdin().lock()).unwrap_or_else(|err| {
+          eprintln!("error: cannot load stdin: {err}");
+          process::exit(1);
+        })
+      } else {
+        None
+      };
+      piped.unwrap_or_else(|| random_world(&config))
+    }
+  };
+  let (width, height) = (current_map.width(), current_map.height());
+  let mut canvas = ConsoleCanvas::new(width, height);
+  let checkpointer = config
+    .checkpoint_every
+    .map(|interval| Checkpointer::new(interval, &config.checkpoint_dir));
+  let frame_delay = config.fps.map(frame_delay);
+  // Instant reads the wasi monotonic clock
+  let run_start = Instant::now();
+  while current_map.generation() < config.generations {
+    let frame_start = Instant::now();
+    let changed = current_map.next_generation(&mut canvas);
+    let generation = current_map.generation();
+    if let Some(checkpointer) = &checkpointer {
+      if let Err(err) = checkpointer.maybe_checkpoint(&current_map, generation) {
+        eprintln!("error: cannot write checkpoint: {err}");
+        process::exit(1);
       }
-      let _ = buf.write(b"\n");
     }
-  }
-}
-
-pub trait ProductSingletonCandidate<F, S> {
-  const FST: F;
-  const SND: S;
-}
-
-impl ProductSingletonCandidate<Self, Self> for u8 {
-  const FST: Self = ON_COLOUR;
-  const SND: Self = OFF_COLOUR;
-}
-
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct NeighbourCount(u8);
-
-impl NeighbourCount {
-  pub const MAX: u8 = 8;
-  pub const MIN: u8 = 0;
-
-  #[inline]
-  pub fn get(self) -> u8 {
-    self.0
-  }
-}
-
-impl TryFrom<u8> for NeighbourCount {
-  type Error = String;
-
-  #[inline]
-  fn try_from(byte: u8) -> Result<Self, Self::Error> {
-    match byte {
-      Self::MIN..=Self::MAX => Ok(Self(byte)),
-      _ => Err(String::from("byte out of range for neighbour count")),
+    if config.render {
+      if let Err(err) = render_frame(&canvas, generation) {
+        // nowhere left to draw, e.g. the wasi host closed stdout
+        eprintln!("error: {err}");
+        process::exit(1);
+      }
     }
-  }
-}
-
-#[derive(Debug, Default, Clone, Copy, PartialEq)]
-pub struct Cell(u8);
-
-impl Cell {
-  pub const MAX: u8 = 0b00011111;
-  pub const MIN: u8 = 0;
-
-  #[inline]
-  pub fn is_alive(&self) -> bool {
-    // state is not 0
-    (self.0 & 0x1) != 0
-  }
-
-  #[inline]
-  pub fn is_empty(&self) -> bool {
-    self.0 == 0
-  }
-
-  #[inline]
-  pub fn set_alive(&mut self) {
-    self.0 |= 0x1;
-  }
-
-  #[inline]
-  pub fn set_dead(&mut self) {
-    self.0 &= !0x1;
-  }
-
-  #[inline]
-  pub fn neighbours(&self) -> NeighbourCount {
-    let count = (self.0 & 0x1e) >> 1;
-    NeighbourCount::try_from(count).unwrap()
-  }
-
-  #[inline]
-  pub fn try_increment(&mut self) -> bool {
-    let neighbour_count = self.neighbours().get();
-    if neighbour_count < NeighbourCount::MAX {
-      *self = Self((self.0 & 0xe1) | ((neighbour_count + 1) << 1));
-      true
-    } else {
-      false
+    // a board that stopped changing will never change again
+    if changed == 0 {
+      break;
     }
-  }
-
-  #[inline]
-  pub fn try_decrement(&mut self) -> bool {
-    let neighbour_count = self.neighbours().get();
-    if neighbour_count > NeighbourCount::MIN {
-      *self = Self((self.0 & 0xe1) | ((neighbour_count - 1) << 1));
-      true
-    } else {
-      false
+    if let Some(max_population) = config.max_population {
+      let population = current_map.population();
+      if population > max_population {
+        eprintln!("population {population} passed --max-population at generation {generation}");
+        break;
+      }
     }
-  }
-}
-
-impl TryFrom<u8> for Cell {
-  type Error = String;
-
-  #[inline]
-  fn try_from(byte: u8) -> Result<Self, Self::Error> {
-    match byte {
-      Self::MIN..=Self::MAX => Ok(Self(byte)),
-      _ => Err(String::from("byte out of range for cell")),
+    // only the time left over after stepping and drawing is slept; under wasi this is a
+    // poll_oneoff clock wait, and std panics if the host refuses it, which aborts the run
+    if let Some(delay) = frame_delay {
+      thread::sleep(delay.saturating_sub(frame_start.elapsed()));
     }
   }
-}
-
-pub struct World {
-  cells: Vec<Cell>,
-  temp_cells: Vec<Cell>,
-  width: usize,
-  height: usize,
-}
-
-impl World {
-  pub fn new(width: usize, height: usize) -> Self {
-    let cell_count = width * height;
-    Self {
-      cells: vec![Cell::default(); cell_count],
-      temp_cells: vec![Cell::default(); cell_count],
-      width,
-      height,
-    }
+  let elapsed = run_start.elapsed();
+  println!("Total generations: {}", current_map.generation());
+  println!("Elapsed: {:.3}s", elapsed.as_secs_f64());
+  if let Some(rate) = generations_per_second(current_map.generation(), elapsed) {
+    println!("Generations per second: {rate:.1}");
   }
-
-  pub fn random<R>(width: usize, height: usize, rng: &mut R) -> Self
-  where
-    R: Rng,
-  {
-    let mut world = World::new(width, height);
-    let init_length = (world.height * world.width) / 2;
-    for _ in 0..init_length {
-      let i = rng.gen_range(0..world.height);
-      let j = rng.gen_range(0..world.width);
-      if !world.cells[i * world.width + j].is_alive() {
-        world.set_cell(i, j);
-      }
+  if config.ascii {
+    if let Err(err) = current_map.write_ascii(&mut io::stdout().lock()) {
+      eprintln!("error: {err}");
+      process::exit(1);
     }
-    world
   }
+}
 
-  pub fn next_generation<Co, Ca>(&mut self, canvas: &mut Ca)
-  where
-    Co: ProductSingletonCandidate<Co, Co>,
-    Ca: Canvas<Colour = Co>,
-  {
-    self.temp_cells.clone_from_slice(&self.cells);
-    for i in 0..self.height {
-      let mut j = 0;
-      while j < self.width {
-        let curr_cell = self.temp_cells[i * self.width + j];
-        // skim past off cells with no neighbours
-        if curr_cell.is_empty() {
-          j += 1;
-          continue;
-        }
-        let count = curr_cell.neighbours().get();
-        if curr_cell.is_alive() {
-          // cell active; turn off if doesnt have 2 or 3 neighbours
-          if count != 2 && count != 3 {
-            self.clear_cell(i, j);
-            canvas.draw_pixel(i, j, Co::SND);
-          }
-        } else {
-          // cell inactive; turn on if has exactly 3 neighbours
-          if count == 3 {
-            self.set_cell(i, j);
-            canvas.draw_pixel(i, j, Co::FST);
-          }
-        }
-        j += 1;
-      }
-    }
-  }
+fn random_world(config: &Config) -> World {
+  let seed = config.seed.unwrap_or_else(rand::random);
+  let mut world = World::try_new(config.width, config.height).unwrap_or_else(|err| {
+    eprintln!("error: {err}");
+    process::exit(1);
+  });
+  world.populate(0.5, &mut StdRng::seed_from_u64(seed));
+  world
+}
 
-  fn set_cell(&mut self, i: usize, j: usize) {
-    let w = self.width;
-    let cell_ptr = i * w + j;
-    // cell is alive
-    self.cells[cell_ptr].set_alive();
-    for &i_offset in &[-1, 0, 1] {
-      for &j_offset in &[-1, 0, 1] {
-        // skip self
-        if i_offset == 0 && j_offset == 0 {
-          continue;
-        }
-        // update neighbours; because we change the neighbours in place, we rely on self
-        // being up to date with the current context -> cannot use ping-pong buffers for cells and temp_cells
-        if let Some((i, j)) = self.as_valid_position(i as isize + i_offset, j as isize + j_offset) {
-          self.cells[i * w + j].try_increment();
-        }
-      }
-    }
+// a pattern piped in whole; none when nothing was piped, so the board can be random instead
+fn read_pattern(mut reader: impl Read) -> io::Result<Option<World>> {
+  let mut contents = String::new();
+  reader.read_to_string(&mut contents)?;
+  if contents.trim().is_empty() {
+    return Ok(None);
   }
+  World::from_pattern_str(&contents).map(Some)
+}
 
-  fn clear_cell(&mut self, i: usize, j: usize) {
-    let w = self.width;
-    let cell_ptr = i * w + j;
-    // cell is dead
-    self.cells[cell_ptr].set_dead();
-    for &i_offset in &[-1, 0, 1] {
-      for &j_offset in &[-1, 0, 1] {
-        // skip self
-        if i_offset == 0 && j_offset == 0 {
-          continue;
-        }
-        // update neighbours
-        if let Some((i, j)) = self.as_valid_position(i as isize + i_offset, j as isize + j_offset) {
-          self.cells[i * w + j].try_decrement();
-        }
-      }
-    }
-  }
+fn render_frame<Ca: Canvas>(canvas: &Ca, generation: u64) -> io::Result<()> {
+  use std::io::Write;
+  let mut stdout = io::stdout().lock();
+  write!(stdout, "\x1B[2J\x1B[1;1H")?;
+  writeln!(stdout, "Generation: {generation}")?;
+  canvas.render()
+}
 
-  #[inline]
-  fn as_valid_position(&self, neighbour_i: isize, neighbour_j: isize) -> Option<(usize, usize)> {
-    if neighbour_i < 0
-      || neighbour_i >= self.height as isize
-      || neighbour_j < 0
-      || neighbour_j >= self.width as isize
-    {
-      None
-    } else {
-      Some((neighbour_i as usize, neighbour_j as usize))
-    }
-  }
+// none when no time was measured, as a clock too coarse for a short run reports
+fn generations_per_second(generations: u64, elapsed: Duration) -> Option<f64> {
+  let secs = elapsed.as_secs_f64();
+  (secs > 0.0).then(|| generations as f64 / secs)
 }
 
-pub fn main() {
-  let (width, height) = (96, 96);
-  let mut current_map = {
-    let seed = rand::random::<u64>();
-    let mut rng = StdRng::seed_from_u64(seed);
-    World::random(width, height, &mut rng)
-  };
-  let mut canvas = ConsoleCanvas::new(width, height);
-  let mut generation: u64 = 0;
-  let render = false;
-  loop {
-    generation += 1;
-    current_map.next_generation(&mut canvas);
-    if render {
-      print!("\x1B[2J\x1B[1;1H");
-      println!("Generation: {generation}");
-      canvas.render();
-    }
-    if generation > 40 {
-      break;
-    }
-  }
-  println!("Total generations: {generation}");
+// time each frame gets at the given rate; fps is never 0, config rejects it
+fn frame_delay(fps: u32) -> Duration {
+  Duration::from_secs(1) / fps
 }
@@ -0,0 +1,37 @@
+// which surrounding cells count as neighbours
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Neighbourhood {
+  // the eight cells of the surrounding 3x3 ring
+  #[default]
+  Moore,
+  // only the four orthogonally adjacent cells
+  VonNeumann,
+}
+
+impl Neighbourhood {
+  // (i, j) offsets of the neighbours from the cell
+  #[inline]
+  pub fn offsets(self) -> &'static [(isize, isize)] {
+    match self {
+      Neighbourhood::Moore => &[
+        (-1, -1),
+        (-1, 0),
+        (-1, 1),
+        (0, -1),
+        (0, 1),
+        (1, -1),
+        (1, 0),
+        (1, 1),
+      ],
+      Neighbourhood::VonNeumann => &[(-1, 0), (0, -1), (0, 1), (1, 0)],
+    }
+  }
+
+  // the most neighbours a cell can have; World::set_neighbourhood clears rule entries past it,
+  // though a count is always stored in the room NeighbourCount::MAX leaves
+  #[inline]
+  pub fn max_count(self) -> u8 {
+    self.offsets().len() as u8
+  }
+}
@@ -0,0 +1,38 @@
+use alloc::vec::Vec;
+
+use rand::Rng;
+
+use crate::World;
+
+impl World {
+  // a random board of blobs rather than uniform static: random values on a lattice of points
+  // scale cells apart are smoothly interpolated into a density for every cell, so blobs and the
+  // gaps between them are roughly scale cells across. a scale of 1 is close to a uniform fill,
+  // and 0 is treated as 1
+  pub fn random_clustered<R>(width: usize, height: usize, rng: &mut R, scale: usize) -> Self
+  where
+    R: Rng,
+  {
+    let scale = scale.max(1);
+    let (rows, cols) = (height / scale + 2, width / scale + 2);
+    let lattice: Vec<f64> = (0..rows * cols).map(|_| rng.gen()).collect();
+    let at = |r: usize, c: usize| lattice[r * cols + c];
+    World::random_with(width, height, rng, |i, j| {
+      let (r, c) = (i / scale, j / scale);
+      let fy = smoothstep((i % scale) as f64 / scale as f64);
+      let fx = smoothstep((j % scale) as f64 / scale as f64);
+      let top = at(r, c) + (at(r, c + 1) - at(r, c)) * fx;
+      let bottom = at(r + 1, c) + (at(r + 1, c + 1) - at(r + 1, c)) * fx;
+      let noise = top + (bottom - top) * fy;
+      // interpolation pulls values toward the middle, so stretch them back out; the excess past
+      // 0 and 1 leaves blob centres full and the gaps empty
+      (noise - 0.5) * 2.0 + 0.5
+    })
+  }
+}
+
+// eases the interpolation so the lattice grid does not show through
+#[inline]
+fn smoothstep(t: f64) -> f64 {
+  t * t * (3.0 - 2.0 * t)
+}
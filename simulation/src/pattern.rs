@@ -0,0 +1,183 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
+use crate::World;
+
+// live cells as (row, column) offsets from the pattern's top left corner
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+  cells: Cow<'static, [(usize, usize)]>,
+}
+
+impl Pattern {
+  pub const BLOCK: Pattern = Pattern::from_static(&[(0, 0), (0, 1), (1, 0), (1, 1)]);
+
+  pub const BLINKER: Pattern = Pattern::from_static(&[(0, 0), (0, 1), (0, 2)]);
+
+  // travels down and to the right
+  pub const GLIDER: Pattern = Pattern::from_static(&[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+
+  // travels to the left
+  pub const LWSS: Pattern = Pattern::from_static(&[
+    (0, 1),
+    (0, 4),
+    (1, 0),
+    (2, 0),
+    (2, 4),
+    (3, 0),
+    (3, 1),
+    (3, 2),
+    (3, 3),
+  ]);
+
+  // 36x9, fires a glider down and to the right every 30 generations
+  pub const GOSPER_GLIDER_GUN: Pattern = Pattern::from_static(&[
+    (0, 24),
+    (1, 22),
+    (1, 24),
+    (2, 12),
+    (2, 13),
+    (2, 20),
+    (2, 21),
+    (2, 34),
+    (2, 35),
+    (3, 11),
+    (3, 15),
+    (3, 20),
+    (3, 21),
+    (3, 34),
+    (3, 35),
+    (4, 0),
+    (4, 1),
+    (4, 10),
+    (4, 16),
+    (4, 20),
+    (4, 21),
+    (5, 0),
+    (5, 1),
+    (5, 10),
+    (5, 14),
+    (5, 16),
+    (5, 17),
+    (5, 22),
+    (5, 24),
+    (6, 10),
+    (6, 16),
+    (6, 24),
+    (7, 11),
+    (7, 15),
+    (8, 12),
+    (8, 13),
+  ]);
+
+  pub fn new(cells: Vec<(usize, usize)>) -> Self {
+    Self {
+      cells: Cow::Owned(cells),
+    }
+  }
+
+  pub const fn from_static(cells: &'static [(usize, usize)]) -> Self {
+    Self {
+      cells: Cow::Borrowed(cells),
+    }
+  }
+
+  #[inline]
+  pub fn cells(&self) -> &[(usize, usize)] {
+    &self.cells
+  }
+
+  // transforms keep the pattern anchored at its top left corner and list cells in row-major order
+
+  pub fn rotate_cw(&self) -> Self {
+    let (height, _) = self.extent();
+    self.map(|(i, j)| (j, height - 1 - i))
+  }
+
+  pub fn rotate_ccw(&self) -> Self {
+    let (_, width) = self.extent();
+    self.map(|(i, j)| (width - 1 - j, i))
+  }
+
+  // mirrors left to right
+  pub fn flip_horizontal(&self) -> Self {
+    let (_, width) = self.extent();
+    self.map(|(i, j)| (i, width - 1 - j))
+  }
+
+  // mirrors top to bottom
+  pub fn flip_vertical(&self) -> Self {
+    let (height, _) = self.extent();
+    self.map(|(i, j)| (height - 1 - i, j))
+  }
+
+  // rows and columns spanned from the origin
+  fn extent(&self) -> (usize, usize) {
+    self
+      .cells
+      .iter()
+      .fold((0, 0), |(h, w), &(i, j)| (h.max(i + 1), w.max(j + 1)))
+  }
+
+  fn map<F>(&self, f: F) -> Self
+  where
+    F: Fn((usize, usize)) -> (usize, usize),
+  {
+    let mut cells: Vec<_> = self.cells.iter().map(|&cell| f(cell)).collect();
+    cells.sort_unstable();
+    Self::new(cells)
+  }
+}
+
+impl World {
+  // top left origins where the board matches the pattern exactly across its bounding box, live
+  // cells alive and every other cell dead, in row-major order; the pattern must fit on the board
+  pub fn find_pattern(&self, pat: &Pattern) -> Vec<(usize, usize)> {
+    let (height, width) = pat.extent();
+    if pat.cells().is_empty() || height > self.height || width > self.width {
+      return Vec::new();
+    }
+    let mut found = Vec::new();
+    for origin_i in 0..=self.height - height {
+      for origin_j in 0..=self.width - width {
+        let matches = pat
+          .cells()
+          .iter()
+          .all(|&(i, j)| self.get(origin_i + i, origin_j + j))
+          && self.population_in(
+            origin_i,
+            origin_j,
+            origin_i + height - 1,
+            origin_j + width - 1,
+          ) == pat.cells().len();
+        if matches {
+          found.push((origin_i, origin_j));
+        }
+      }
+    }
+    found
+  }
+
+  // sets or clears the brush's cells with the middle of its bounding box on (center_i,
+  // center_j); cells landing outside the board are skipped
+  pub fn paint(&mut self, brush: &Pattern, center_i: usize, center_j: usize, alive: bool) {
+    let (height, width) = brush.extent();
+    for &(di, dj) in brush.cells() {
+      let i = (center_i + di).checked_sub(height / 2);
+      let j = (center_j + dj).checked_sub(width / 2);
+      if let (Some(i), Some(j)) = (i, j) {
+        self.set(i, j, alive);
+      }
+    }
+  }
+
+  // cells landing outside the board are skipped
+  pub fn insert_pattern(&mut self, pat: &Pattern, origin_i: usize, origin_j: usize) {
+    for &(di, dj) in pat.cells() {
+      let (i, j) = (origin_i + di, origin_j + dj);
+      if i < self.height && j < self.width {
+        self.set_cell(i, j);
+      }
+    }
+  }
+}
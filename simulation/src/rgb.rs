@@ -0,0 +1,62 @@
+use std::io::{self, BufWriter, Stdout, Write};
+
+use crate::{Canvas, ProductSingletonCandidate};
+
+pub type Rgb = (u8, u8, u8);
+
+pub(crate) const ON_RGB: Rgb = (255, 255, 255); // on-cell pixel color
+pub(crate) const OFF_RGB: Rgb = (0, 0, 0); // off-cell pixel color
+
+impl ProductSingletonCandidate<Self, Self> for Rgb {
+  const FST: Self = ON_RGB;
+  const SND: Self = OFF_RGB;
+}
+
+// keeps a true colour per cell and renders it to the terminal with ansi 24-bit escapes
+pub struct RgbCanvas {
+  width: usize,
+  height: usize,
+  pixels: Vec<Rgb>,
+  stdout: Stdout,
+}
+
+impl RgbCanvas {
+  pub fn new(width: usize, height: usize) -> Self {
+    Self {
+      width,
+      height,
+      pixels: vec![OFF_RGB; width * height],
+      stdout: io::stdout(),
+    }
+  }
+
+  #[inline]
+  pub fn pixel(&self, i: usize, j: usize) -> Rgb {
+    self.pixels[i * self.width + j]
+  }
+}
+
+impl Canvas for RgbCanvas {
+  type Colour = Rgb;
+
+  #[inline]
+  fn draw_pixel(&mut self, i: usize, j: usize, colour: Self::Colour) {
+    self.pixels[i * self.width + j] = colour;
+  }
+
+  fn render(&self) -> io::Result<()> {
+    let mut buf = BufWriter::new(self.stdout.lock());
+    for row in self.pixels.chunks(self.width.max(1)).take(self.height) {
+      for &(r, g, b) in row {
+        write!(buf, "\x1B[48;2;{r};{g};{b}m  ")?;
+      }
+      buf.write_all(b"\x1B[0m\n")?;
+    }
+    buf.flush()
+  }
+
+  #[inline]
+  fn dimensions(&self) -> Option<(usize, usize)> {
+    Some((self.width, self.height))
+  }
+}
@@ -0,0 +1,195 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+
+use crate::{Cell, Rule, Topology, World, WorldError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RleError {
+  MissingHeader,
+  InvalidHeader(String),
+  InvalidRule(String),
+  UnexpectedToken(char),
+  OutOfBounds { i: usize, j: usize },
+  // the header asks for a board that cannot be allocated
+  World(WorldError),
+}
+
+impl fmt::Display for RleError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      RleError::MissingHeader => write!(f, "rle pattern is missing its `x = .., y = ..` header"),
+      RleError::InvalidHeader(header) => write!(f, "invalid rle header '{header}'"),
+      RleError::InvalidRule(reason) => write!(f, "invalid rle rule: {reason}"),
+      RleError::UnexpectedToken(c) => write!(f, "unexpected token '{c}' in rle body"),
+      RleError::OutOfBounds { i, j } => {
+        write!(f, "live cell ({i}, {j}) lies outside the rle bounds")
+      }
+      RleError::World(err) => write!(f, "{err}"),
+    }
+  }
+}
+
+impl Error for RleError {}
+
+// what an rle file says about its pattern beyond the cells
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PatternMeta {
+  // from the `#N` line
+  pub name: Option<String>,
+  // every `#C` or `#c` line, in order
+  pub comments: Vec<String>,
+  // from the header's `rule =`, or failing that a `#r` line; the world runs it either way
+  pub rule: Option<Rule>,
+}
+
+impl World {
+  pub fn from_rle(rle: &str) -> Result<World, RleError> {
+    Self::from_rle_with_meta(rle).map(|(world, _)| world)
+  }
+
+  pub fn from_rle_with_meta(rle: &str) -> Result<(World, PatternMeta), RleError> {
+    let mut meta = PatternMeta::default();
+    let (comment_lines, mut lines): (Vec<&str>, Vec<&str>) = rle
+      .lines()
+      .map(str::trim)
+      .filter(|line| !line.is_empty())
+      .partition(|line| line.starts_with('#'));
+    for line in comment_lines {
+      let text = line.get(2..).unwrap_or_default().trim();
+      match line.get(..2) {
+        Some("#N") => meta.name = Some(text.to_string()),
+        Some("#C" | "#c") => meta.comments.push(text.to_string()),
+        Some("#r") => meta.rule = Some(Rule::parse(text).map_err(RleError::InvalidRule)?),
+        // other tags, such as `#O` for the author, are skipped
+        _ => {}
+      }
+    }
+    if lines.is_empty() {
+      return Err(RleError::MissingHeader);
+    }
+    let header = lines.remove(0);
+    let (width, height, header_rule) = parse_header(header)?;
+    meta.rule = header_rule.or(meta.rule);
+    // sizes come straight from the input, so an absurd header is an error rather than a panic
+    let mut world = World::try_from_parts(
+      width,
+      height,
+      meta.rule.unwrap_or_default(),
+      Topology::default(),
+    )
+    .map_err(RleError::World)?;
+    let (mut i, mut j) = (0usize, 0usize);
+    // pending run count, 0 meaning no count was given
+    let mut run = 0usize;
+    for c in lines.into_iter().flat_map(str::chars) {
+      match c {
+        '0'..='9' => {
+          let digit = c as usize - '0' as usize;
+          run = run.saturating_mul(10).saturating_add(digit);
+          continue;
+        }
+        'b' | '.' => j = j.saturating_add(run.max(1)),
+        // anything other than a dead tag is a live state
+        'o' | 'A'..='Z' => {
+          let end = j.saturating_add(run.max(1));
+          if i >= height || end > width {
+            return Err(RleError::OutOfBounds { i, j: end - 1 });
+          }
+          for j in j..end {
+            world.set_cell(i, j);
+          }
+          j = end;
+        }
+        '$' => {
+          i = i.saturating_add(run.max(1));
+          j = 0;
+        }
+        '!' => break,
+        c if c.is_whitespace() => {}
+        c => return Err(RleError::UnexpectedToken(c)),
+      }
+      run = 0;
+    }
+    Ok((world, meta))
+  }
+
+  pub fn to_rle(&self) -> String {
+    let mut tokens = Vec::new();
+    // row the encoder is currently positioned on
+    let mut cursor = 0;
+    for i in 0..self.height {
+      let row = &self.cells[i * self.width..(i + 1) * self.width];
+      // trailing dead cells are implied, so empty rows only show up in the `$` counts
+      if !row.iter().any(Cell::is_alive) {
+        continue;
+      }
+      if i > cursor {
+        tokens.push(run_token(i - cursor, '$'));
+        cursor = i;
+      }
+      let mut j = 0;
+      while j < self.width {
+        let alive = row[j].is_alive();
+        let len = row[j..]
+          .iter()
+          .take_while(|c| c.is_alive() == alive)
+          .count();
+        if alive || j + len < self.width {
+          tokens.push(run_token(len, if alive { 'o' } else { 'b' }));
+        }
+        j += len;
+      }
+    }
+    tokens.push(String::from("!"));
+
+    let mut rle = format!(
+      "x = {}, y = {}, rule = {}\n",
+      self.width, self.height, self.rule
+    );
+    let mut line_len = 0;
+    for token in tokens {
+      if line_len + token.len() > MAX_LINE_LEN {
+        rle.push('\n');
+        line_len = 0;
+      }
+      line_len += token.len();
+      rle.push_str(&token);
+    }
+    rle.push('\n');
+    rle
+  }
+}
+
+// rle lines should not exceed 70 characters
+const MAX_LINE_LEN: usize = 70;
+
+fn run_token(count: usize, tag: char) -> String {
+  if count == 1 {
+    tag.to_string()
+  } else {
+    format!("{count}{tag}")
+  }
+}
+
+// parses `x = 3, y = 3, rule = B3/S23`, where the rule is optional
+fn parse_header(header: &str) -> Result<(usize, usize, Option<Rule>), RleError> {
+  let invalid = || RleError::InvalidHeader(header.to_string());
+  let (mut width, mut height, mut rule) = (None, None, None);
+  for field in header.split(',') {
+    let (key, value) = field.split_once('=').ok_or_else(invalid)?;
+    let value = value.trim();
+    match key.trim() {
+      "x" => width = Some(value.parse().map_err(|_| invalid())?),
+      "y" => height = Some(value.parse().map_err(|_| invalid())?),
+      "rule" => rule = Some(Rule::parse(value).map_err(RleError::InvalidRule)?),
+      _ => return Err(invalid()),
+    }
+  }
+  match (width, height) {
+    (Some(width), Some(height)) => Ok((width, height, rule)),
+    _ => Err(invalid()),
+  }
+}
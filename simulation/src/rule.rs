@@ -0,0 +1,65 @@
+use alloc::format;
+use alloc::string::String;
+use core::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rule {
+  pub born: [bool; 9],
+  pub survive: [bool; 9],
+}
+
+impl Rule {
+  // B3/S23
+  pub const CONWAY: Rule = Rule {
+    born: [false, false, false, true, false, false, false, false, false],
+    survive: [false, false, true, true, false, false, false, false, false],
+  };
+
+  // parses B/S notation (`B36/S23`), or the older S/B notation (`23/36`)
+  pub fn parse(rulestring: &str) -> Result<Self, String> {
+    let (first, second) = rulestring
+      .trim()
+      .split_once('/')
+      .ok_or_else(|| format!("rulestring '{rulestring}' is missing a '/'"))?;
+    let (born, survive) = match (first.chars().next(), second.chars().next()) {
+      (Some('B' | 'b'), Some('S' | 's')) => (&first[1..], &second[1..]),
+      (Some('S' | 's'), Some('B' | 'b')) => (&second[1..], &first[1..]),
+      _ => (second, first),
+    };
+    Ok(Self {
+      born: Self::parse_counts(born)?,
+      survive: Self::parse_counts(survive)?,
+    })
+  }
+
+  fn parse_counts(digits: &str) -> Result<[bool; 9], String> {
+    let mut counts = [false; 9];
+    for c in digits.chars() {
+      match c.to_digit(10) {
+        Some(n @ 0..=8) => counts[n as usize] = true,
+        _ => return Err(format!("invalid neighbour count '{c}' in rulestring")),
+      }
+    }
+    Ok(counts)
+  }
+}
+
+impl Default for Rule {
+  fn default() -> Self {
+    Self::CONWAY
+  }
+}
+
+impl fmt::Display for Rule {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "B")?;
+    for (n, _) in self.born.iter().enumerate().filter(|(_, &b)| b) {
+      write!(f, "{n}")?;
+    }
+    write!(f, "/S")?;
+    for (n, _) in self.survive.iter().enumerate().filter(|(_, &s)| s) {
+      write!(f, "{n}")?;
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,53 @@
+use alloc::vec::Vec;
+
+use wide::u8x16;
+
+use crate::{Cell, NeighbourCount, World};
+
+const LANES: usize = 16;
+
+impl World {
+  // decides a row 16 cells at a time by matching each packed (alive | neighbours << 1) byte
+  // against the states the rule toggles; those never carry decay bits, so dying cells never
+  // match. wide lowers to sse/neon/simd128 where the target has them and to plain scalar code
+  // elsewhere, so results never depend on the target
+  pub(crate) fn row_changes(
+    &self,
+    i: usize,
+    row: &[Cell],
+    changes: &mut Vec<(usize, usize, bool)>,
+  ) {
+    let mut toggles = [u8x16::splat(0); 2 * (NeighbourCount::MAX as usize + 1)];
+    let mut toggle_count = 0;
+    for count in NeighbourCount::MIN..=NeighbourCount::MAX {
+      for state in [count << 1, (count << 1) | 0x1] {
+        if self.transition(Cell(state)).is_some() {
+          toggles[toggle_count] = u8x16::splat(state);
+          toggle_count += 1;
+        }
+      }
+    }
+    let toggles = &toggles[..toggle_count];
+
+    let mut chunks = row.chunks_exact(LANES);
+    for (n, chunk) in chunks.by_ref().enumerate() {
+      let states = u8x16::new(core::array::from_fn(|k| chunk[k].0));
+      let toggled = toggles
+        .iter()
+        .fold(u8x16::splat(0), |acc, &toggle| acc | states.cmp_eq(toggle));
+      let mut lanes = toggled.move_mask();
+      while lanes != 0 {
+        let k = lanes.trailing_zeros() as usize;
+        lanes &= lanes - 1;
+        changes.push((i, n * LANES + k, !chunk[k].is_alive()));
+      }
+    }
+    // the tail that does not fill a whole vector goes through the scalar path
+    let offset = row.len() - chunks.remainder().len();
+    for (k, &cell) in chunks.remainder().iter().enumerate() {
+      if let Some(alive) = self.transition(cell) {
+        changes.push((i, offset + k, alive));
+      }
+    }
+  }
+}
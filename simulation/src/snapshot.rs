@@ -0,0 +1,51 @@
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Neighbourhood, Rule, Topology, World};
+
+// the serialized form only carries the alive bits; neighbour counts are derived on load
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+  width: usize,
+  height: usize,
+  rule: String,
+  topology: Topology,
+  // absent from snapshots written before neighbourhoods existed, which were all moore
+  #[serde(default)]
+  neighbourhood: Neighbourhood,
+  alive: Vec<u8>,
+}
+
+impl Serialize for World {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    Snapshot {
+      width: self.width,
+      height: self.height,
+      rule: self.rule.to_string(),
+      topology: self.topology,
+      neighbourhood: self.neighbourhood,
+      alive: self.alive_bitmap(),
+    }
+    .serialize(serializer)
+  }
+}
+
+impl<'de> Deserialize<'de> for World {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    let snapshot = Snapshot::deserialize(deserializer)?;
+    let rule = Rule::parse(&snapshot.rule).map_err(D::Error::custom)?;
+    let cell_count = snapshot
+      .width
+      .checked_mul(snapshot.height)
+      .ok_or_else(|| D::Error::custom("world dimensions overflow"))?;
+    if snapshot.alive.len() != cell_count.div_ceil(8) {
+      return Err(D::Error::custom(
+        "alive bitmap does not match world dimensions",
+      ));
+    }
+    let mut world = World::from_parts(snapshot.width, snapshot.height, rule, snapshot.topology);
+    world.set_neighbourhood(snapshot.neighbourhood);
+    world.set_alive_bitmap(&snapshot.alive);
+    Ok(world)
+  }
+}
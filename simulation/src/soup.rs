@@ -0,0 +1,51 @@
+use alloc::vec::Vec;
+
+use rand::Rng;
+
+use crate::{StableOutcome, World};
+
+// generations looked past the end of a run for motion; gliders and the common spaceships repeat
+// their shape within this many
+const MOTION_WINDOW: u64 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SoupResult {
+  // World::random_from_seed(n, n, seed) rebuilds the soup
+  pub seed: u64,
+  pub outcome: StableOutcome,
+  pub final_population: usize,
+  // the bounding box was still shifting at an unchanged population once the run ended, as it does
+  // when gliders or spaceships fly off
+  pub moved: bool,
+}
+
+// runs `trials` random n x n soups until they settle or `generations` have passed; each soup sits
+// in the middle of a bounded board with room for a glider to fly the whole run without reaching
+// the edge, so escaping gliders keep the soup unsettled rather than crashing into debris
+pub fn soup_search<R>(n: usize, trials: usize, rng: &mut R, generations: u64) -> Vec<SoupResult>
+where
+  R: Rng,
+{
+  (0..trials)
+    .map(|_| {
+      let seed = rng.gen();
+      let mut world = World::random_from_seed(n, n, seed);
+      let margin = (generations / 4 + MOTION_WINDOW) as usize;
+      let side = n + 2 * margin;
+      world.resize(side, side, margin as isize, margin as isize);
+      let outcome = world.run_until_stable(generations);
+      let final_population = world.population();
+      let bounds = world.bounding_box();
+      let moved = matches!(outcome, StableOutcome::Unsettled { .. }) && {
+        world.run(MOTION_WINDOW, |_, _| {});
+        world.population() == final_population && world.bounding_box() != bounds
+      };
+      SoupResult {
+        seed,
+        outcome,
+        final_population,
+        moved,
+      }
+    })
+    .collect()
+}
@@ -0,0 +1,129 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{Canvas, ProductSingletonCandidate, Rule, Topology, World};
+
+// stores only the live cells, for huge boards with few of them; births on zero neighbours (B0)
+// cannot be represented and are ignored
+pub struct SparseWorld {
+  alive: HashSet<(usize, usize)>,
+  width: usize,
+  height: usize,
+  rule: Rule,
+  topology: Topology,
+}
+
+impl SparseWorld {
+  pub fn new(width: usize, height: usize) -> Self {
+    Self::with_rule(width, height, Rule::default())
+  }
+
+  pub fn with_rule(width: usize, height: usize, rule: Rule) -> Self {
+    Self::from_parts(width, height, rule, Topology::default())
+  }
+
+  pub fn with_topology(width: usize, height: usize, topology: Topology) -> Self {
+    Self::from_parts(width, height, Rule::default(), topology)
+  }
+
+  fn from_parts(width: usize, height: usize, rule: Rule, topology: Topology) -> Self {
+    Self {
+      alive: HashSet::new(),
+      width,
+      height,
+      rule,
+      topology,
+    }
+  }
+
+  #[inline]
+  pub fn alive(&self) -> &HashSet<(usize, usize)> {
+    &self.alive
+  }
+
+  #[inline]
+  pub fn population(&self) -> usize {
+    self.alive.len()
+  }
+
+  // out of range cells are ignored
+  pub fn set(&mut self, i: usize, j: usize, alive: bool) {
+    if i >= self.height || j >= self.width {
+      return;
+    }
+    if alive {
+      self.alive.insert((i, j));
+    } else {
+      self.alive.remove(&(i, j));
+    }
+  }
+
+  pub fn next_generation<Co, Ca>(&mut self, canvas: &mut Ca) -> usize
+  where
+    Co: ProductSingletonCandidate<Co, Co>,
+    Ca: Canvas<Colour = Co>,
+  {
+    self.evolve(|i, j, alive| {
+      canvas.draw_pixel(i, j, if alive { Co::FST } else { Co::SND });
+    })
+  }
+
+  pub fn step(&mut self) -> usize {
+    self.evolve(|_, _, _| {})
+  }
+
+  fn evolve<F>(&mut self, mut on_change: F) -> usize
+  where
+    F: FnMut(usize, usize, bool),
+  {
+    // only live cells and their neighbours can change
+    let mut counts: HashMap<(usize, usize), u8> = HashMap::new();
+    for &(i, j) in &self.alive {
+      counts.entry((i, j)).or_insert(0);
+      for i_offset in -1..=1 {
+        for j_offset in -1..=1 {
+          if i_offset == 0 && j_offset == 0 {
+            continue;
+          }
+          let neighbour = self.topology.resolve(
+            i as isize + i_offset,
+            j as isize + j_offset,
+            self.height,
+            self.width,
+          );
+          if let Some(neighbour) = neighbour {
+            *counts.entry(neighbour).or_insert(0) += 1;
+          }
+        }
+      }
+    }
+    let mut changes: Vec<(usize, usize, bool)> = counts
+      .into_iter()
+      .filter_map(|(pos, count)| {
+        let alive = self.alive.contains(&pos);
+        let next = if alive {
+          self.rule.survive[count as usize]
+        } else {
+          self.rule.born[count as usize]
+        };
+        (next != alive).then_some((pos.0, pos.1, next))
+      })
+      .collect();
+    // report changes in the same row-major order as the dense world
+    changes.sort_unstable();
+    for &(i, j, alive) in &changes {
+      self.set(i, j, alive);
+      on_change(i, j, alive);
+    }
+    changes.len()
+  }
+}
+
+// dying cells under a GenerationsRule are dropped, along with the state count; the sparse world
+// always counts the moore neighbourhood
+impl From<&World> for SparseWorld {
+  fn from(world: &World) -> Self {
+    let mut sparse = Self::from_parts(world.width, world.height, world.rule, world.topology);
+    sparse.alive.extend(world.live_cells());
+    sparse
+  }
+}
@@ -0,0 +1,19 @@
+use futures::stream::{self, Stream};
+
+use crate::World;
+
+impl World {
+  // one item per generation for the next `generations` generations: the generation just reached
+  // and the (i, j) of every cell that toggled on the way. stepping happens when an item is
+  // polled, so a consumer that stops polling stops the simulation
+  pub fn into_stream(self, generations: u64) -> impl Stream<Item = (u64, Vec<(usize, usize)>)> {
+    stream::unfold((self, 0), move |(mut world, stepped)| async move {
+      if stepped == generations {
+        return None;
+      }
+      world.step();
+      let changes = world.changes.iter().map(|&(i, j, _)| (i, j)).collect();
+      Some(((world.generation, changes), (world, stepped + 1)))
+    })
+  }
+}
@@ -0,0 +1,48 @@
+use rand::Rng;
+
+use crate::World;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+  // left half mirrored onto the right, so (i, j) matches (i, width - 1 - j)
+  Horizontal,
+  // top half mirrored onto the bottom, so (i, j) matches (height - 1 - i, j)
+  Vertical,
+  // top left quadrant mirrored both ways
+  Quad,
+}
+
+impl World {
+  // fills one half or quadrant with a fair coin per cell and mirrors it; every mirrored cell goes
+  // through set_cell, so neighbour counts stay consistent
+  pub fn random_symmetric<R>(width: usize, height: usize, rng: &mut R, axis: Symmetry) -> Self
+  where
+    R: Rng,
+  {
+    let mut world = World::new(width, height);
+    let (rows, cols) = match axis {
+      Symmetry::Horizontal => (height, width.div_ceil(2)),
+      Symmetry::Vertical => (height.div_ceil(2), width),
+      Symmetry::Quad => (height.div_ceil(2), width.div_ceil(2)),
+    };
+    for i in 0..rows {
+      for j in 0..cols {
+        if !rng.gen_bool(0.5) {
+          continue;
+        }
+        let (mirror_i, mirror_j) = (height - 1 - i, width - 1 - j);
+        world.set_cell(i, j);
+        match axis {
+          Symmetry::Horizontal => world.set_cell(i, mirror_j),
+          Symmetry::Vertical => world.set_cell(mirror_i, j),
+          Symmetry::Quad => {
+            world.set_cell(i, mirror_j);
+            world.set_cell(mirror_i, j);
+            world.set_cell(mirror_i, mirror_j);
+          }
+        }
+      }
+    }
+    world
+  }
+}
@@ -0,0 +1,45 @@
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use crate::World;
+
+#[derive(Serialize)]
+struct Record {
+  generation: u64,
+  population: usize,
+  births: usize,
+  deaths: usize,
+  // [min_i, min_j, max_i, max_j], null on an empty board
+  bounding_box: Option<(usize, usize, usize, usize)>,
+}
+
+// writes one json object per line describing a generation; report it from the hook passed to
+// World::run, with the error kept for after the run since the hook cannot return one
+pub struct JsonlReporter<W: Write> {
+  writer: W,
+}
+
+impl<W: Write> JsonlReporter<W> {
+  pub fn new(writer: W) -> Self {
+    Self { writer }
+  }
+
+  // the generation the world just reached, with the births and deaths that led to it
+  pub fn report(&mut self, world: &World) -> io::Result<()> {
+    let stats = world.generation_stats();
+    let record = Record {
+      generation: world.generation(),
+      population: world.population(),
+      births: stats.births,
+      deaths: stats.deaths,
+      bounding_box: world.bounding_box(),
+    };
+    serde_json::to_writer(&mut self.writer, &record)?;
+    self.writer.write_all(b"\n")
+  }
+
+  pub fn into_inner(self) -> W {
+    self.writer
+  }
+}
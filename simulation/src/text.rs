@@ -0,0 +1,183 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+use crate::{Rule, Topology, World, WorldError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+  Empty,
+  UnexpectedChar {
+    line: usize,
+    c: char,
+  },
+  MissingHeader,
+  InvalidCoordinate {
+    line: usize,
+  },
+  RaggedRow {
+    line: usize,
+    expected: usize,
+    found: usize,
+  },
+  // the rows ask for a board that cannot be allocated
+  World(WorldError),
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ParseError::Empty => write!(f, "pattern contains no rows"),
+      ParseError::UnexpectedChar { line, c } => {
+        write!(f, "unexpected character '{c}' on line {line}")
+      }
+      ParseError::MissingHeader => write!(f, "missing `#Life 1.06` header"),
+      ParseError::InvalidCoordinate { line } => {
+        write!(f, "expected an `x y` coordinate pair on line {line}")
+      }
+      ParseError::RaggedRow {
+        line,
+        expected,
+        found,
+      } => write!(f, "line {line} has {found} cells, expected {expected}"),
+      ParseError::World(err) => write!(f, "{err}"),
+    }
+  }
+}
+
+impl Error for ParseError {}
+
+impl World {
+  // plaintext `.cells` format; `.` is dead, `O` is alive, `!` starts a comment line
+  pub fn from_cells(cells: &str) -> Result<World, ParseError> {
+    let mut rows: Vec<(usize, &str)> = cells
+      .lines()
+      .enumerate()
+      .filter(|(_, line)| !line.starts_with('!'))
+      .map(|(n, line)| (n + 1, line.trim_end()))
+      .collect();
+    while rows.last().is_some_and(|(_, row)| row.is_empty()) {
+      rows.pop();
+    }
+    if rows.is_empty() {
+      return Err(ParseError::Empty);
+    }
+    // rows may omit their trailing dead cells
+    let width = rows
+      .iter()
+      .map(|(_, row)| row.chars().count())
+      .max()
+      .unwrap_or(0);
+    // a short input can still ask for a huge board, so running out of room is an error
+    let mut world = World::try_from_parts(width, rows.len(), Rule::default(), Topology::default())
+      .map_err(ParseError::World)?;
+    for (i, &(line, row)) in rows.iter().enumerate() {
+      for (j, c) in row.chars().enumerate() {
+        match c {
+          'O' => world.set_cell(i, j),
+          '.' => {}
+          c => return Err(ParseError::UnexpectedChar { line, c }),
+        }
+      }
+    }
+    Ok(world)
+  }
+
+  // life 1.06 `x y` pairs, possibly negative; each lands at row y + origin.0, column x + origin.1
+  // and cells that fall outside the board are clipped
+  pub fn from_life106(
+    life: &str,
+    width: usize,
+    height: usize,
+    origin: (isize, isize),
+  ) -> Result<World, ParseError> {
+    let mut lines = life.lines().enumerate();
+    if lines.next().map(|(_, header)| header.trim_end()) != Some("#Life 1.06") {
+      return Err(ParseError::MissingHeader);
+    }
+    let mut world = World::new(width, height);
+    for (n, line) in lines {
+      let line = line.trim();
+      if line.is_empty() {
+        continue;
+      }
+      let invalid = || ParseError::InvalidCoordinate { line: n + 1 };
+      let mut coords = line.split_whitespace().map(str::parse::<isize>);
+      let (Some(Ok(x)), Some(Ok(y)), None) = (coords.next(), coords.next(), coords.next()) else {
+        return Err(invalid());
+      };
+      let (i, j) = (y.saturating_add(origin.0), x.saturating_add(origin.1));
+      if i >= 0 && j >= 0 {
+        world.set(i as usize, j as usize, true);
+      }
+    }
+    Ok(world)
+  }
+
+  pub fn to_cells(&self) -> String {
+    let mut cells = String::with_capacity((self.width + 1) * self.height);
+    for row in self.cells.chunks(self.width.max(1)) {
+      cells.extend(row.iter().map(|c| if c.is_alive() { 'O' } else { '.' }));
+      cells.push('\n');
+    }
+    cells
+  }
+
+  // the `1`/`0` rows written by write_ascii; every row must be as wide as the first
+  pub fn from_ascii(ascii: &str) -> Result<World, ParseError> {
+    let rows: Vec<&str> = ascii.lines().map(str::trim_end).collect();
+    let Some(first) = rows.first() else {
+      return Err(ParseError::Empty);
+    };
+    let width = first.chars().count();
+    let mut world = World::new(width, rows.len());
+    for (i, row) in rows.iter().enumerate() {
+      let found = row.chars().count();
+      if found != width {
+        return Err(ParseError::RaggedRow {
+          line: i + 1,
+          expected: width,
+          found,
+        });
+      }
+      for (j, c) in row.chars().enumerate() {
+        match c {
+          '1' => world.set_cell(i, j),
+          '0' => {}
+          c => return Err(ParseError::UnexpectedChar { line: i + 1, c }),
+        }
+      }
+    }
+    Ok(world)
+  }
+
+  // one `1`/`0` per cell, rows separated by newlines
+  #[cfg(feature = "std")]
+  pub fn write_ascii(&self, w: &mut impl Write) -> io::Result<()> {
+    for row in self.cells.chunks(self.width.max(1)) {
+      let line: Vec<u8> = row
+        .iter()
+        .map(|c| if c.is_alive() { b'1' } else { b'0' })
+        .collect();
+      w.write_all(&line)?;
+      w.write_all(b"\n")?;
+    }
+    Ok(())
+  }
+}
+
+// `#` for live cells and `.` for the rest, one line per row, for pasting into notes and logs
+impl fmt::Display for World {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for row in self.cells.chunks(self.width.max(1)) {
+      for cell in row {
+        f.write_str(if cell.is_alive() { "#" } else { "." })?;
+      }
+      f.write_str("\n")?;
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,56 @@
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Topology {
+  // neighbours past the edge do not exist
+  #[default]
+  Bounded,
+  // rows and columns wrap around onto the opposite edge
+  Toroidal,
+  // columns wrap around, the top and bottom edges are bounded
+  Cylinder,
+  // rows wrap around with the columns mirrored, the left and right edges are bounded
+  Mobius,
+}
+
+impl Topology {
+  // maps a possibly out of range position onto the grid, if it lands on one
+  #[inline]
+  pub fn resolve(self, i: isize, j: isize, height: usize, width: usize) -> Option<(usize, usize)> {
+    let (h, w) = (height as isize, width as isize);
+    match self {
+      Topology::Bounded => {
+        if i < 0 || i >= h || j < 0 || j >= w {
+          None
+        } else {
+          Some((i as usize, j as usize))
+        }
+      }
+      Topology::Toroidal => {
+        if h == 0 || w == 0 {
+          None
+        } else {
+          Some((i.rem_euclid(h) as usize, j.rem_euclid(w) as usize))
+        }
+      }
+      Topology::Cylinder => {
+        if i < 0 || i >= h || w == 0 {
+          None
+        } else {
+          Some((i as usize, j.rem_euclid(w) as usize))
+        }
+      }
+      Topology::Mobius => {
+        if j < 0 || j >= w || h == 0 {
+          None
+        } else if (0..h).contains(&i) {
+          Some((i as usize, j as usize))
+        } else {
+          // every trip past the top or bottom edge mirrors the strip once more
+          let flipped = i.div_euclid(h) % 2 != 0;
+          let j = if flipped { w - 1 - j } else { j };
+          Some((i.rem_euclid(h) as usize, j as usize))
+        }
+      }
+    }
+  }
+}